@@ -0,0 +1,109 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use sandbox::{entities::World, events::Event, rng::Rng, ruleset::Ruleset, sim::Sim, Game};
+
+//the same teams the tests play with
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+//the same games every iteration, so runs can be compared. divide by GAMES for the time per game
+const GAMES: u64 = 16;
+
+fn play(mut game: Game, world: &mut World, rng: &mut Rng) -> f64 {
+    let mut sim = Sim::new(world, rng);
+    loop {
+        let evt = sim.next(&game);
+        evt.apply(&mut game, sim.world);
+        if let Event::GameOver = evt {
+            return black_box(game.scoreboard.home_team.score);
+        }
+    }
+}
+
+//from Game::new to GameOver
+fn games(c: &mut Criterion) {
+    let (world, home, away) = common::world();
+    c.bench_function("16 games", |b| {
+        b.iter_batched(
+            || (1..=GAMES).map(|seed| (world.clone(), Rng::new(seed, 420))).collect::<Vec<_>>(),
+            |runs| {
+                for (mut world, mut rng) in runs {
+                    let game = Game::new(home, away, 0, None, &world, &mut rng);
+                    play(game, &mut world, &mut rng);
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+//the tick loop alone
+fn ticks(c: &mut Criterion) {
+    let (world, home, away) = common::world();
+    c.bench_function("16 games without setup", |b| {
+        b.iter_batched(
+            || {
+                (1..=GAMES).map(|seed| {
+                    let mut rng = Rng::new(seed, 420);
+                    let game = Game::new(home, away, 0, None, &world, &mut rng);
+                    (world.clone(), rng, game)
+                }).collect::<Vec<_>>()
+            },
+            |runs| {
+                for (mut world, mut rng, game) in runs {
+                    play(game, &mut world, &mut rng);
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+//a regular season between generated teams, every team playing every day
+const TEAMS: usize = 20;
+const DAYS: usize = 99;
+
+fn league() -> (World, Vec<uuid::Uuid>) {
+    let mut rng = Rng::new(69, 420);
+    let mut world = World::new(Ruleset::new(11).unwrap());
+    let teams = (0..TEAMS).map(|i| world.gen_team(&mut rng, format!("Team {}", i), i.to_string())).collect();
+    (world, teams)
+}
+
+//round robin by rotating everyone but the first team around a circle, home and away swapping every cycle
+fn matchups(teams: &[uuid::Uuid], day: usize) -> impl Iterator<Item = (uuid::Uuid, uuid::Uuid)> + '_ {
+    let n = teams.len();
+    let round = day % (n - 1);
+    let seat = move |i: usize| if i == 0 { teams[0] } else { teams[1 + (i - 1 + round) % (n - 1)] };
+    (0..n / 2).map(move |i| {
+        let (a, b) = (seat(i), seat(n - 1 - i));
+        if (day / (n - 1)) % 2 == 0 { (a, b) } else { (b, a) }
+    })
+}
+
+fn season(c: &mut Criterion) {
+    let (world, teams) = league();
+    let mut group = c.benchmark_group("season");
+    group.sample_size(10);
+    group.bench_function("20 teams, 99 days", |b| {
+        b.iter_batched(
+            || (world.clone(), Rng::new(1, 420)),
+            |(mut world, mut rng)| {
+                for day in 0..DAYS {
+                    for (home, away) in matchups(&teams, day) {
+                        let game = Game::new(home, away, day, None, &world, &mut rng);
+                        play(game, &mut world, &mut rng);
+                    }
+                    world.clear_game();
+                    if day % 9 == 8 {
+                        world.clear_weekly();
+                    }
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, games, ticks, season);
+criterion_main!(benches);
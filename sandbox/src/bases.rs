@@ -1,140 +1,170 @@
+use arrayvec::ArrayVec;
 use uuid::Uuid;
 
+//kept inline so events can carry the runners around without allocating. it's also the number of bits
+//in the advance_marked masks, so it's one runner per base for up to MAX_BASES bases and no more
+pub const MAX_RUNNERS: usize = 8;
+//counting home, so 9 is eight bases to stand on. Baserunners::new won't make any more than that
+pub const MAX_BASES: u8 = MAX_RUNNERS as u8 + 1;
+
 #[derive(Debug, Clone)]
 pub struct Baserunner {
     pub id: Uuid,
     pub base: u8,
+    //whoever was pitching when they got on, who gets charged if they score
+    pub pitcher: Uuid,
 }
 
+//bases are numbered from first base at 0, so with base_number bases home is base_number - 1.
+//nobody ever stands on home: a runner who reaches it scores and leaves the basepaths right away
 #[derive(Debug, Clone)]
 pub struct Baserunners {
-    pub runners: Vec<Baserunner>,
+    pub runners: ArrayVec<Baserunner, MAX_RUNNERS>,
     pub base_number: u8,
 }
 
+//what moving the runners did: everyone who crossed home, in the order they sat in Baserunners
+#[derive(Debug, Clone, Default)]
+pub struct Advancement {
+    pub scored: ArrayVec<Baserunner, MAX_RUNNERS>,
+}
+
+impl Advancement {
+    pub fn runs(&self) -> usize {
+        self.scored.len()
+    }
+
+    pub fn any(&self) -> bool {
+        !self.scored.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Baserunner> {
+        self.scored.iter()
+    }
+
+    pub fn merge(&mut self, other: Advancement) {
+        self.scored.extend(other.scored);
+    }
+}
+
 impl Baserunners {
     pub fn new(bn: u8) -> Baserunners {
+        assert!((2..=MAX_BASES).contains(&bn), "can't have {} bases, it has to be 2 to {}", bn, MAX_BASES);
         Baserunners {
-            runners: Vec::new(),
+            runners: ArrayVec::new(),
             base_number: bn
         }
     }
 
+    pub fn home(&self) -> u8 {
+        self.base_number - 1
+    }
+
     pub fn occupied(&self, base: u8) -> bool {
         self.runners.iter().any(|x| x.base == base)
     }
 
+    //home is never blocked, anyone can always come in to score
     pub fn can_advance(&self, base: u8) -> bool {
-        !self.occupied(base + 1)
+        base + 1 >= self.home() || !self.occupied(base + 1)
     }
 
     pub fn at(&self, base: u8) -> Option<Uuid> {
         self.runners
             .iter()
             .find(|x| x.base == base)
-            .map(|x| x.id.clone())
+            .map(|x| x.id)
     }
 
     pub fn contains(&self, id: Uuid) -> bool {
         self.runners.iter().any(|x| x.id == id)
     }
 
-    pub fn advance(&mut self, base: u8) {
-        for r in self.runners.iter_mut() {
-            if r.base == base {
-                r.base += 1;
-                return;
-            }
+    //the runner on `base` takes one more
+    pub fn advance(&mut self, base: u8) -> Advancement {
+        if let Some(r) = self.runners.iter_mut().find(|r| r.base == base) {
+            r.base += 1;
         }
+        self.settle()
     }
 
     pub fn remove(&mut self, base: u8) -> Option<Uuid> {
-        let idx = self
-            .runners
-            .iter()
-            .enumerate()
-            .find(|x| x.1.base == base)
-            .map(|x| x.0);
-
-        if let Some(idx) = idx {
-            let runner = self.runners.remove(idx);
-            Some(runner.id)
-        } else {
-            None
-        }
+        let idx = self.runners.iter().position(|x| x.base == base)?;
+        Some(self.runners.remove(idx).id)
     }
 
-    pub fn advance_all(&mut self, amount: u8) {
+    pub fn advance_all(&mut self, amount: u8) -> Advancement {
         for r in self.runners.iter_mut() {
             r.base += amount;
         }
+        self.settle()
     }
 
-    pub fn walk(&mut self) {
-        // todo: this code is also crap
-        let mut num_occupied = 0;
-        for i in 0..self.base_number + 1 {
-            if self.occupied(i) {
-                num_occupied += 1;
-            } else {
-                break;
-            }
-        }
-
-        for i in (0..num_occupied).rev() {
-            self.advance(i);
-        }
-    }
-
-    pub fn walk_instincts(&mut self, third: bool) {
-        //todo: the runners who score end up on the wrong base
-        //does this cause problems?
-        //yes past me it does cause problems because of fifth base
-        if third {
-            self.advance_all(3);
-        } else {
-            if self.occupied(0) {
-                self.advance_all(2);
-            } else if self.occupied(1) {
-                self.advance_all(1);
-            }
+    //everyone moves `amount` bases, then the runners marked in the mask (bit i for the i-th runner)
+    //take one more if the next base is free by the time it's their turn
+    pub fn advance_marked(&mut self, amount: u8, mask: u8) -> Advancement {
+        for r in self.runners.iter_mut() {
+            r.base += amount;
         }
-    }
-
-    pub fn advance_if(&mut self, f: impl Fn(&Baserunner) -> bool) {
         for i in 0..self.runners.len() {
-            if self.can_advance(self.runners[i].base) {
-                if f(&self.runners[i]) {
-                    self.runners[i].base += 1;
-                }
+            let base = self.runners[i].base;
+            if mask & (1 << i) != 0 && base < self.home() && self.can_advance(base) {
+                self.runners[i].base += 1;
             }
         }
-    }
-
-    pub fn forced_advance_if(&mut self, f: impl Fn(&Baserunner) -> bool) {
-        if self.occupied(0) && self.occupied(1) && (self.base_number == 4 || self.occupied(2)) {
-            for runner in self.runners.iter_mut() {
-                runner.base += 1;
-            }
-        } else {
-            for i in 0..self.runners.len() {
-                if self.can_advance(self.runners[i].base) {
-                    if f(&self.runners[i]) {
-                        self.runners[i].base += 1;
-                    } else if self.runners[i].base == 0 {
-                        self.walk(); //this is a code crime
-                    }
-                }
-            }
+        self.settle()
+    }
+
+    //a hit: the runners move up, then the batter lands on base `bases - 1`,
+    //which is already home on a quadruple with no fifth base
+    pub fn hit(&mut self, bases: u8, mask: u8, batter: Uuid, pitcher: Uuid) -> Advancement {
+        let mut advancement = self.advance_marked(bases, mask);
+        self.runners.push(Baserunner { id: batter, base: bases - 1, pitcher });
+        advancement.merge(self.settle());
+        advancement
+    }
+
+    //the batter takes `base` and pushes along whoever's in the way: everyone behind them
+    //has to end up past them, and every runner pushed onto an occupied base pushes that runner too.
+    //a walk is force(0, ..), instincts walk to 1 or 2
+    pub fn force(&mut self, base: u8, batter: Uuid, pitcher: Uuid) -> Advancement {
+        let mut order: ArrayVec<usize, MAX_RUNNERS> = (0..self.runners.len()).collect();
+        order.sort_by_key(|&i| self.runners[i].base);
+        let mut free = base + 1;
+        for i in order {
+            let runner = &mut self.runners[i];
+            runner.base = runner.base.max(free);
+            free = runner.base + 1;
         }
+        let advancement = self.settle();
+        self.runners.push(Baserunner { id: batter, base, pitcher });
+        advancement
+    }
+
+    //everyone who's made it home comes off the basepaths
+    fn settle(&mut self) -> Advancement {
+        let home = self.home();
+        let mut advancement = Advancement::default();
+        self.runners.retain(|r| {
+            if r.base >= home {
+                advancement.scored.push(r.clone());
+                false
+            } else {
+                true
+            }
+        });
+        advancement
     }
 
-    pub fn add(&mut self, base: u8, id: Uuid) {
-        self.runners.push(Baserunner { id, base });
+    //a runner put back on the field, like the batter on a fielder's choice.
+    //nobody gets added on home, use hit or force for the batter
+    pub fn add(&mut self, base: u8, id: Uuid, pitcher: Uuid) {
+        debug_assert!(base < self.home());
+        self.runners.push(Baserunner { id, base, pitcher });
     }
 
     pub fn empty(&self) -> bool {
-        self.runners.len() == 0
+        self.runners.is_empty()
     }
 
     pub fn len(&self) -> usize {
@@ -158,27 +188,16 @@ impl Baserunners {
         }
     }
 
+    //the lead runner of the ones forced from first, who gets thrown out on a fielder's choice
     pub fn pick_runner_fc(&self) -> u8 {
-        if self.occupied(1) {
-            if self.occupied(2) {
-                return 2;
-            } else {
-                return 1;
-            }
-        } else {
-            return 0;
-        }
+        (1..self.home()).take_while(|&base| self.occupied(base)).last().unwrap_or(0)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &Baserunner> {
         self.runners.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Baserunner> {
-        self.runners.iter_mut()
-    }
-
     pub fn clear(&mut self) {
-        self.runners = Vec::new();
+        self.runners.clear();
     }
 }
@@ -0,0 +1,107 @@
+//probabilities we haven't pinned down yet, kept out of sim.rs so they can be changed without recompiling
+//the file format is one `name = value` per line, # starts a comment
+//version identifies a set of values, bump it whenever the defaults change. a file from another version
+//is refused rather than mixed in
+pub const CONSTANTS_VERSION: u32 = 1;
+
+//every constant once, as `name: default`. this makes the struct, the defaults, and the lookups
+//by name that reading and writing files go through, so a new constant only needs a line here.
+//defaults can depend on the season's ruleset, which goes by the name given up front
+macro_rules! constants {
+    (|$season:ident| { $($name:ident: $default:expr,)* }) => {
+        #[derive(Clone, Debug)]
+        pub struct Constants {
+            $(pub $name: f64,)*
+        }
+
+        impl Constants {
+            pub fn new($season: u8) -> Constants {
+                Constants {
+                    $($name: $default,)*
+                }
+            }
+
+            fn value_mut(&mut self, name: &str) -> Option<&mut f64> {
+                match name {
+                    $(stringify!($name) => Some(&mut self.$name),)*
+                    _ => None
+                }
+            }
+
+            pub fn values(&self) -> Vec<(&'static str, f64)> {
+                vec![$((stringify!($name), self.$name),)*]
+            }
+        }
+    };
+}
+
+constants!(|season_ruleset| {
+    base_instincts: 0.2,
+    reverberating: 0.2,
+    haunted: 0.2,
+    fire_eater: 0.002,
+    unstable_incineration: 0.002,
+    big_peanut: 0.000002,
+    birds: 0.03,
+    shelled_pecking: 0.00015,
+    bird_seed_pecking: 0.001,
+    reverb: 0.00003,
+    //reverb type thresholds are cumulative, the rest goes to type 3
+    reverb_type_0: 0.09,
+    reverb_type_1: 0.55,
+    reverb_type_2: 0.95,
+    siphon: 0.0025,
+    siphon_negative: 0.35,
+    siphon_batting: 0.5,
+    siphon_pitching: 0.8,
+    night_shift: 0.01,
+    salmon: 0.1375,
+    salmon_runs_lost: 0.675,
+    salmon_both_lost: 0.2,
+    debt_hbp: 0.02,
+    mild_pitch: 0.005,
+    flooding_swept: 0.1,
+    party: if season_ruleset < 20 { 0.0055 } else { 0.00525 },
+    //electric teams zapping a strike off their batter, or a ball off the other team's
+    zap: 0.2,
+});
+
+impl Constants {
+    //a single `name = value` override, as it appears in a file
+    pub fn set(&mut self, line: &str) -> Result<(), String> {
+        let (name, value) = line.split_once('=').ok_or_else(|| format!("expected name = value, got \"{}\"", line))?;
+        let name = name.trim();
+        let value = value.trim();
+        //values from another version were picked against other defaults, so they don't mix with these
+        if name == "version" {
+            let version: u32 = value.parse().map_err(|_| format!("bad version \"{}\"", value))?;
+            if version != CONSTANTS_VERSION {
+                return Err(format!("constants are version {}, this build is version {}", version, CONSTANTS_VERSION));
+            }
+            return Ok(());
+        }
+        let parsed: f64 = value.parse().map_err(|_| format!("bad value for {}: \"{}\"", name, value))?;
+        *self.value_mut(name).ok_or_else(|| format!("unknown constant {}", name))? = parsed;
+        Ok(())
+    }
+
+    //overrides every constant in the text, anything not mentioned keeps its current value
+    pub fn read(&mut self, text: &str) -> Result<(), String> {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            self.set(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn write(&self) -> String {
+        let mut text = format!("version = {}\n", CONSTANTS_VERSION);
+        for (name, value) in self.values() {
+            text.push_str(&format!("{} = {}\n", name, value));
+        }
+        text
+    }
+}
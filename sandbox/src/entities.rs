@@ -1,26 +1,48 @@
-use std::{collections::BTreeMap, f64::consts::PI};
+use std::{collections::HashMap, f64::consts::PI, hash::{BuildHasherDefault, Hasher}, sync::OnceLock};
 
+use strum::{EnumString, IntoStaticStr};
 use uuid::Uuid;
 
-use crate::{events::Events, mods::{Mod, ModLifetime, Mods}, rng::Rng};
+use crate::{events::Events, mods::{Mod, ModLifetime, Mods}, rng::{Rng, Roll}, rotation, ruleset::Ruleset};
+
+//uuids are random already, so instead of hashing them properly this just mixes their bytes.
+//players and teams get looked up dozens of times a pitch and this is a lot quicker than a BTreeMap
+#[derive(Clone, Copy, Default)]
+pub struct UuidHasher(u64);
+
+impl Hasher for UuidHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.0 = (self.0.rotate_left(5) ^ u64::from_le_bytes(word)).wrapping_mul(0x517cc1b727220a95);
+        }
+    }
+}
+
+//iteration order is arbitrary, sort by id where it matters
+pub type UuidMap<V> = HashMap<Uuid, V, BuildHasherDefault<UuidHasher>>;
 
 #[derive(Clone, Debug)]
 pub struct World {
-    pub players: BTreeMap<Uuid, Player>,
-    pub teams: BTreeMap<Uuid, Team>,
-    pub stadiums: BTreeMap<Uuid, Stadium>,
+    pub players: UuidMap<Player>,
+    pub teams: UuidMap<Team>,
+    pub stadiums: UuidMap<Stadium>,
     pub hall: Vec<Uuid>, //think of this as a view into a section of players
-    pub season_ruleset: u8,
+    pub ruleset: Ruleset,
 }
 
 impl World {
-    pub fn new(season_ruleset: u8) -> World {
+    pub fn new(ruleset: Ruleset) -> World {
         World {
-            players: BTreeMap::new(),
-            teams: BTreeMap::new(),
-            stadiums: BTreeMap::new(),
+            players: UuidMap::default(),
+            teams: UuidMap::default(),
+            stadiums: UuidMap::default(),
             hall: Vec::new(),
-            season_ruleset
+            ruleset
         }
     }
     pub fn player(&self, id: Uuid) -> &Player {
@@ -65,6 +87,10 @@ impl World {
         self.teams.insert(team.id, team);
     }
 
+    pub fn insert_stadium(&mut self, stadium: Stadium) {
+        self.stadiums.insert(stadium.id, stadium);
+    }
+
     pub fn replace_player(&mut self, player_id: Uuid, new_player_id: Uuid) {
         let player = self.player_mut(player_id);
         let team_id = player.team.unwrap();
@@ -105,14 +131,20 @@ impl World {
             lineup: Vec::new(),
             rotation: Vec::new(),
             shadows: Vec::new(),
+            bench_size: 0,
+            rotation_slot: 0,
             name,
             wins: 0,
             losses: 0,
             postseason_wins: 0,
             postseason_losses: 0,
+            shames: 0,
+            shamings: 0,
+            shame_runs: 0.0,
             partying: false,
             fate: 100,
             mods: Mods::new(),
+            stadium: None,
         };
 
         for _ in 0..9 {
@@ -126,33 +158,25 @@ impl World {
         for _ in 0..11 {
             team.shadows.push(self.gen_player(rng, id));
         }
+        team.bench_size = team.shadows.len();
 
         self.insert_team(team);
         id
     }
 
     pub fn gen_player(&mut self, rng: &mut Rng, team: Uuid) -> Uuid {
-        let interview_rolls = 6 + 2; //soul, allergy, fate, ritual, blood, coffee + names
-        let mut player = Player::new(rng);
-        let id = player.id;
-        player.name = format!("Player {}", &(player.id).to_string()[..8]);
-        for _ in 0..interview_rolls {
-            rng.next(); //to make the rng align
-        }
-        player.team = Some(team.clone());
-        self.insert_player(player);
-        id
+        let player = Player::new(rng);
+        self.add_rolled_player(player, team)
     }
 
     pub fn add_rolled_player(&mut self, mut player: Player, team: Uuid) -> Uuid {
         let id = player.id;
-        player.name = format!("Player {}", &(player.id).to_string()[..8]);
         player.team = Some(team.clone());
         self.insert_player(player);
         id
     }
 
-    pub fn random_hall_player(&self, rng: &mut Rng) -> Uuid {
+    pub fn random_hall_player(&self, rng: &mut impl Roll) -> Uuid {
         let index = rng.index(self.hall.len());
         self.hall[index]
     }
@@ -161,18 +185,46 @@ impl World {
         for (_, player) in self.players.iter_mut() {
             player.mods.clear_game();
         }
+        for (_, team) in self.teams.iter_mut() {
+            team.mods.clear_game();
+        }
     }
     
     pub fn clear_weekly(&mut self) {
         for (_, player) in self.players.iter_mut() {
             player.mods.clear_weekly();
         }
+        for (_, team) in self.teams.iter_mut() {
+            team.mods.clear_weekly();
+        }
     }
 
     pub fn clear_season(&mut self) {
         for (_, player) in self.players.iter_mut() {
             player.mods.clear_season();
         }
+        for (_, team) in self.teams.iter_mut() {
+            team.mods.clear_season();
+        }
+    }
+}
+
+//vibes get looked up for nearly every stat on every pitch and the sin was most of the cost.
+//the frequency is a whole number, so the phases for buoyancy between 0 and 1 fit in a table
+const PHASE_FREQUENCIES: std::ops::RangeInclusive<usize> = 6..=16;
+const PHASE_DAYS: usize = 128;
+
+fn vibes_phase(frequency: f64, day: usize) -> f64 {
+    let phase = |frequency: f64, day: usize| (PI * ((2.0 / frequency) * (day as f64) + 0.5)).sin();
+    static TABLE: OnceLock<Vec<[f64; PHASE_DAYS]>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        PHASE_FREQUENCIES.map(|frequency| std::array::from_fn(|day| phase(frequency as f64, day))).collect()
+    });
+    let row = frequency as usize;
+    if frequency.fract() == 0.0 && PHASE_FREQUENCIES.contains(&row) && day < PHASE_DAYS {
+        table[row - PHASE_FREQUENCIES.start()][day]
+    } else {
+        phase(frequency, day)
     }
 }
 
@@ -193,7 +245,7 @@ impl<'a> NameGen<'a> {
             last_name_length: 538,
         }
     }
-    pub fn generate(&self, rng: &mut Rng) -> String {
+    pub fn generate(&self, rng: &mut impl Roll) -> String {
         let first_name_index = (rng.next() * self.first_name_length as f64).floor() as usize;
         let last_name_index = (rng.next() * self.last_name_length as f64).floor() as usize;
         let mut name = self.first_names[first_name_index].to_string();
@@ -203,6 +255,87 @@ impl<'a> NameGen<'a> {
     }
 }
 
+impl NameGen<'static> {
+    //the name lists are built in, so every generated player can share one copy
+    pub fn shared() -> &'static NameGen<'static> {
+        static NAME_GEN: OnceLock<NameGen<'static>> = OnceLock::new();
+        NAME_GEN.get_or_init(NameGen::new)
+    }
+}
+
+//chronicler stores these as indices into ALL
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Blood {
+    A,
+    AAA,
+    AA,
+    Acidic,
+    Basic,
+    O,
+    ONo,
+    H2O,
+    Electric,
+    Love,
+    Fire,
+    Psychic,
+    Grass,
+}
+
+impl Blood {
+    pub const ALL: [Blood; 13] = [
+        Blood::A,
+        Blood::AAA,
+        Blood::AA,
+        Blood::Acidic,
+        Blood::Basic,
+        Blood::O,
+        Blood::ONo,
+        Blood::H2O,
+        Blood::Electric,
+        Blood::Love,
+        Blood::Fire,
+        Blood::Psychic,
+        Blood::Grass,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coffee {
+    Black,
+    LightAndSweet,
+    Macchiato,
+    CreamAndSugar,
+    ColdBrew,
+    FlatWhite,
+    Americano,
+    Espresso,
+    HeavyFoam,
+    Latte,
+    Decaf,
+    MilkSubstitute,
+    PlentyOfSugar,
+    Anything,
+}
+
+impl Coffee {
+    pub const ALL: [Coffee; 14] = [
+        Coffee::Black,
+        Coffee::LightAndSweet,
+        Coffee::Macchiato,
+        Coffee::CreamAndSugar,
+        Coffee::ColdBrew,
+        Coffee::FlatWhite,
+        Coffee::Americano,
+        Coffee::Espresso,
+        Coffee::HeavyFoam,
+        Coffee::Latte,
+        Coffee::Decaf,
+        Coffee::MilkSubstitute,
+        Coffee::PlentyOfSugar,
+        Coffee::Anything,
+    ];
+}
+
 // use this for like multiplier calc or something
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -236,6 +369,35 @@ pub enum PlayerAttr {
 }
 
 impl PlayerAttr {
+    pub const ALL: [PlayerAttr; 26] = [
+        PlayerAttr::Buoyancy,
+        PlayerAttr::Divinity,
+        PlayerAttr::Martyrdom,
+        PlayerAttr::Moxie,
+        PlayerAttr::Musclitude,
+        PlayerAttr::Patheticism,
+        PlayerAttr::Thwackability,
+        PlayerAttr::Tragicness,
+        PlayerAttr::Coldness,
+        PlayerAttr::Overpowerment,
+        PlayerAttr::Ruthlessness,
+        PlayerAttr::Shakespearianism,
+        PlayerAttr::Suppression,
+        PlayerAttr::Unthwackability,
+        PlayerAttr::BaseThirst,
+        PlayerAttr::Continuation,
+        PlayerAttr::GroundFriction,
+        PlayerAttr::Indulgence,
+        PlayerAttr::Laserlikeness,
+        PlayerAttr::Anticapitalism,
+        PlayerAttr::Chasiness,
+        PlayerAttr::Omniscience,
+        PlayerAttr::Tenaciousness,
+        PlayerAttr::Watchfulness,
+        PlayerAttr::Pressurization,
+        PlayerAttr::Cinnamon,
+    ];
+
     pub fn discr(&self) -> u8 {
         *self as u8
     }
@@ -268,6 +430,76 @@ impl PlayerAttr {
             false
         }
     }
+    pub fn category(&self) -> AttrCategory {
+        if self.is_batting() {
+            AttrCategory::Batting
+        } else if self.is_pitching() {
+            AttrCategory::Pitching
+        } else if self.is_running() {
+            AttrCategory::Baserunning
+        } else if self.is_defense() {
+            AttrCategory::Defense
+        } else {
+            AttrCategory::Vibes
+        }
+    }
+    pub fn in_category(category: AttrCategory) -> impl Iterator<Item = PlayerAttr> {
+        PlayerAttr::ALL.into_iter().filter(move |attr| attr.category() == category)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttrCategory {
+    Batting,
+    Pitching,
+    Baserunning,
+    Defense,
+    Vibes,
+}
+
+impl AttrCategory {
+    //in the order blooddrain rolls them
+    pub const DRAINABLE: [AttrCategory; 4] = [
+        AttrCategory::Pitching,
+        AttrCategory::Batting,
+        AttrCategory::Defense,
+        AttrCategory::Baserunning,
+    ];
+}
+
+//stat changes indexed by PlayerAttr. positive always means better,
+//so boosting patheticism or tragicness lowers them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Boosts([f64; 26]);
+
+impl Boosts {
+    pub fn new() -> Boosts {
+        Boosts::default()
+    }
+    pub fn all(amount: f64) -> Boosts {
+        Boosts([amount; 26])
+    }
+    pub fn category(category: AttrCategory, amount: f64) -> Boosts {
+        let mut boosts = Boosts::new();
+        for attr in PlayerAttr::in_category(category) {
+            boosts.set(attr, amount);
+        }
+        boosts
+    }
+    //base + up to threshold for every stat in attrs, rolled in that order
+    pub fn roll<I: IntoIterator<Item = PlayerAttr>>(rng: &mut impl Roll, base: f64, threshold: f64, attrs: I) -> Boosts {
+        let mut boosts = Boosts::new();
+        for attr in attrs {
+            boosts.set(attr, base + rng.next() * threshold);
+        }
+        boosts
+    }
+    pub fn get(&self, attr: PlayerAttr) -> f64 {
+        self.0[attr as usize]
+    }
+    pub fn set(&mut self, attr: PlayerAttr, amount: f64) {
+        self.0[attr as usize] = amount;
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -276,136 +508,128 @@ pub struct Player {
     pub name: String,
     pub mods: Mods,
     pub legendary_item: Option<LegendaryItem>,
+    //item ids as chronicler has them, kept so they export the way they came in. the sim only reads legendary_item
+    pub bat: Option<String>,
+    pub armor: Option<String>,
     pub team: Option<Uuid>, //ig
     
     pub feed: Events,
     pub swept_on: Option<usize>,
     pub scattered_letters: u8,
 
-    // stats??
-    // todo: maybe represent stats with an array
-    // to make rolling new players less awkward
-    pub buoyancy: f64,
-    pub divinity: f64,
-    pub martyrdom: f64,
-    pub moxie: f64,
-    pub musclitude: f64,
-    pub patheticism: f64,
-    pub thwackability: f64,
-    pub tragicness: f64,
-
-    pub coldness: f64,
-    pub overpowerment: f64,
-    pub ruthlessness: f64,
-    pub shakespearianism: f64,
-    pub suppression: f64,
-    pub unthwackability: f64,
-
-    pub base_thirst: f64,
-    pub continuation: f64,
-    pub ground_friction: f64,
-    pub indulgence: f64,
-    pub laserlikeness: f64,
-
-    pub anticapitalism: f64,
-    pub chasiness: f64,
-    pub omniscience: f64,
-    pub tenaciousness: f64,
-    pub watchfulness: f64,
-
-    pub pressurization: f64,
-    pub cinnamon: f64,
+    //indexed by PlayerAttr, see get and set
+    pub stats: [f64; 26],
+
+    //from the interview
+    pub soul: u8,
+    pub peanut_allergy: Option<bool>, //none if chronicler doesn't say
+    pub fate: u8,
+    pub ritual: String, //empty for generated players, see Player::new
+    pub blood: Option<Blood>,
+    pub coffee: Option<Coffee>,
 }
 
 impl Player {
-    pub fn new(rng: &mut Rng) -> Player {
+    pub fn new(rng: &mut impl Roll) -> Player {
         let id = Uuid::new_v4();
 
-        Player {
+        let mut player = Player {
             id,
-            name: "".to_string(), //todo: name gen
+            name: String::new(),
             mods: Mods::new(),
             legendary_item: None,
+            bat: None,
+            armor: None,
             team: None,
 
             feed: Events::new(),
             swept_on: None,
             scattered_letters: 0,
 
-            // NOW it's rng order compatible
-            thwackability: rng.next(),
-            moxie: rng.next(),
-            divinity: rng.next(),
-            musclitude: rng.next(),
-            patheticism: rng.next(),
-            buoyancy: rng.next(),
-            base_thirst: rng.next(),
-            laserlikeness: rng.next(),
-            ground_friction: rng.next(),
-            continuation: rng.next(),
-            indulgence: rng.next(),
-            martyrdom: rng.next(),
-            tragicness: rng.next(),
-            shakespearianism: rng.next(),
-            suppression: rng.next(),
-            unthwackability: rng.next(),
-            coldness: rng.next(),
-            overpowerment: rng.next(),
-            ruthlessness: rng.next(),
-            omniscience: rng.next(),
-            tenaciousness: rng.next(),
-            watchfulness: rng.next(),
-            anticapitalism: rng.next(),
-            chasiness: rng.next(),
-            pressurization: rng.next(),
-            cinnamon: rng.next(),
+            stats: Player::roll_stats(rng),
+
+            soul: 0,
+            peanut_allergy: None,
+            fate: 0,
+            ritual: String::new(),
+            blood: None,
+            coffee: None,
+        };
+        //the interview comes after the stats, then the name
+        player.soul = (rng.next() * 8.0).floor() as u8 + 2;
+        player.peanut_allergy = Some(rng.next() < 0.5);
+        player.fate = (rng.next() * 100.0).floor() as u8;
+        //the ritual takes its roll, but nobody has the list it picks from, so it stays empty
+        rng.next();
+        player.blood = Some(Blood::ALL[rng.index(Blood::ALL.len())]);
+        player.coffee = Some(Coffee::ALL[rng.index(Coffee::ALL.len())]);
+        player.name = NameGen::shared().generate(rng);
+        //a dash is a name nobody knows, which still takes up its roll
+        if player.name.split(' ').any(|part| part == "-") {
+            player.name = format!("Player {}", &id.to_string()[..8]);
+        }
+        player
+    }
+    //new players roll their stats in this order, not PlayerAttr's
+    pub const ROLL_ORDER: [PlayerAttr; 26] = [
+        PlayerAttr::Thwackability,
+        PlayerAttr::Moxie,
+        PlayerAttr::Divinity,
+        PlayerAttr::Musclitude,
+        PlayerAttr::Patheticism,
+        PlayerAttr::Buoyancy,
+        PlayerAttr::BaseThirst,
+        PlayerAttr::Laserlikeness,
+        PlayerAttr::GroundFriction,
+        PlayerAttr::Continuation,
+        PlayerAttr::Indulgence,
+        PlayerAttr::Martyrdom,
+        PlayerAttr::Tragicness,
+        PlayerAttr::Shakespearianism,
+        PlayerAttr::Suppression,
+        PlayerAttr::Unthwackability,
+        PlayerAttr::Coldness,
+        PlayerAttr::Overpowerment,
+        PlayerAttr::Ruthlessness,
+        PlayerAttr::Omniscience,
+        PlayerAttr::Tenaciousness,
+        PlayerAttr::Watchfulness,
+        PlayerAttr::Anticapitalism,
+        PlayerAttr::Chasiness,
+        PlayerAttr::Pressurization,
+        PlayerAttr::Cinnamon,
+    ];
+    fn roll_stats(rng: &mut impl Roll) -> [f64; 26] {
+        let mut stats = [0.0; 26];
+        for attr in Player::ROLL_ORDER {
+            stats[attr as usize] = rng.next();
         }
+        stats
+    }
+    pub fn get(&self, attr: PlayerAttr) -> f64 {
+        self.stats[attr as usize]
+    }
+    pub fn set(&mut self, attr: PlayerAttr, value: f64) {
+        self.stats[attr as usize] = value;
+    }
+    //every stat in a category with its value, in PlayerAttr order
+    pub fn stats_in(&self, category: AttrCategory) -> impl Iterator<Item = (PlayerAttr, f64)> + '_ {
+        PlayerAttr::in_category(category).map(|attr| (attr, self.get(attr)))
     }
     pub fn vibes(&self, day: usize) -> f64 {
         if self.scattered_letters > 0 {
             0.0
         } else {
-            let frequency = 6.0 + (10.0 * self.buoyancy).round();
-            // todo: sin table? do we care that much?
-            let sin_phase = (PI * ((2.0 / frequency) * (day as f64) + 0.5)).sin();
-            0.5 * ((sin_phase - 1.0) * self.pressurization + (sin_phase + 1.0) * self.cinnamon)
+            let frequency = 6.0 + (10.0 * self.get(PlayerAttr::Buoyancy)).round();
+            let sin_phase = vibes_phase(frequency, day);
+            0.5 * ((sin_phase - 1.0) * self.get(PlayerAttr::Pressurization) + (sin_phase + 1.0) * self.get(PlayerAttr::Cinnamon))
         }
     }
-    pub fn boost(&mut self, boosts: &Vec<f64>) {
-        //todo: implement custom boost order
-        self.buoyancy += boosts[0];
-        self.divinity += boosts[1];
-        self.martyrdom += boosts[2];
-        self.moxie += boosts[3];
-        self.musclitude += boosts[4];
-        self.patheticism -= boosts[5];
-        self.thwackability += boosts[6];
-        self.tragicness -= boosts[7];
-                
-        self.coldness += boosts[8];
-        self.overpowerment += boosts[9];
-        self.ruthlessness += boosts[10];
-        self.shakespearianism += boosts[11];
-        self.suppression += boosts[12];
-        self.unthwackability += boosts[13];
-                
-        self.base_thirst += boosts[14];
-        self.continuation += boosts[15];
-        self.ground_friction += boosts[16];
-        self.indulgence += boosts[17];
-        self.laserlikeness += boosts[18];
-                
-        self.anticapitalism += boosts[19];
-        self.chasiness += boosts[20];
-        self.omniscience += boosts[21];
-        self.tenaciousness += boosts[22];
-        self.watchfulness += boosts[23];
-        
-        if boosts.len() == 25 {
-            self.cinnamon += boosts[24];
-        } else {
-            self.pressurization += boosts[24];
-            self.cinnamon += boosts[25];
+    //boosts make the player better, so negative stats go down
+    pub fn boost(&mut self, boosts: &Boosts) {
+        for attr in PlayerAttr::ALL {
+            let amount = boosts.get(attr);
+            self.stats[attr as usize] += if attr.is_negative() { -amount } else { amount };
         }
     }
     pub fn add_legendary_item(&mut self, item: LegendaryItem) {
@@ -433,9 +657,12 @@ impl Player {
     }
 }
 
-#[derive(Clone, Debug)]
+//names are the item ids in the bat/armor fields
+#[derive(Clone, Debug, EnumString, IntoStaticStr)]
+#[strum(serialize_all="SCREAMING_SNAKE_CASE")]
 pub enum LegendaryItem {
     DialTone,
+    #[strum(serialize="LITERAL_ARM_CANNON", serialize="ARM_CANNON")]
     LiteralArmCannon,
     VibeCheck,
     BangersAndSmash,
@@ -443,7 +670,9 @@ pub enum LegendaryItem {
     Mushroom,
     NightVisionGoggles,
     ShrinkRay,
+    #[strum(serialize="THE_IFFEY_JR", serialize="IFFEY_JR")]
     TheIffeyJr,
+    #[strum(serialize="ACTUAL_AIRPLANE", serialize="AIRPLANE")]
     ActualAirplane
 }
 
@@ -456,34 +685,41 @@ pub struct Team {
     pub lineup: Vec<Uuid>,
     pub rotation: Vec<Uuid>,
     pub shadows: Vec<Uuid>,
+    pub bench_size: usize, //the first this many shadows are the bench, the rest the bullpen
+    pub rotation_slot: usize, //see rotation.rs
 
     pub wins: i16,
     pub losses: i16,
     pub postseason_wins: i16,
     pub postseason_losses: i16,
+    pub shames: i16, //times this team got shamed
+    pub shamings: i16, //times this team shamed someone
+    pub shame_runs: f64, //taken off this team's score at the start of their next game, see Mod::TargetedShame
     pub partying: bool,
     pub fate: usize,
 
     pub mods: Mods,
+    pub stadium: Option<Uuid>,
 }
 
 impl Team {
     fn replace_player(&mut self, id: Uuid, new_id: Uuid) {
-        //todo: write this code with return
         if let Some(idx) = self.lineup.iter().position(|x| *x == id) {
             self.lineup[idx] = new_id;
-        } else if let Some(idx) = self.rotation.iter().position(|x| *x == id) {
-            self.rotation[idx] = new_id;
-        } else if let Some(idx) = self.shadows.iter().position(|x| *x == id) {
-            self.shadows[idx] = new_id;
-        } else {
-            panic!("player not found");
+            return;
+        }
+        if rotation::replace(self, id, new_id) {
+            return;
+        }
+        match self.shadows.iter().position(|x| *x == id) {
+            Some(idx) => self.shadows[idx] = new_id,
+            None => panic!("player not found")
         }
     }
 
     //if reverb type is 1 (partial), returns pairs of players to be swapped
     //if not, returns indexes of old slots (lineup lower) in rotation-lineup order
-    pub fn roll_reverb_changes(&self, rng: &mut Rng, reverb_type: u8, gravity_players: &Vec<usize>) -> Vec<usize> {
+    pub fn roll_reverb_changes(&self, rng: &mut impl Roll, reverb_type: u8, gravity_players: &Vec<usize>) -> Vec<usize> {
         let mut reverb_changes = Vec::new();
         let lineup_length = self.lineup.len();
         let rotation_length = self.rotation.len();
@@ -630,20 +866,29 @@ impl Team {
                 panic!("wrong reverb type, somehow");
             }
         }
-        for i in 0..length {
-            if i < lineup_length {
-                self.lineup[i] = result[i];
-            } else {
-                self.rotation[i - lineup_length] = result[i];
-            }
-        }
+        self.lineup.copy_from_slice(&result[..lineup_length]);
+        rotation::shuffle(self, &result[lineup_length..]);
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct Stadium {
     pub id: Uuid,
+    pub team: Option<Uuid>,
 
     pub name: String,
-    // todo: stats ig
+    pub nickname: String,
+
+    //the formulas read these off the home team's stadium, see formulas::park
+    pub mysticism: f64,
+    pub viscosity: f64,
+    pub elongation: f64,
+    pub filthiness: f64,
+    pub obtuseness: f64,
+    pub forwardness: f64,
+    pub grandiosity: f64,
+    pub ominousness: f64,
+    pub fortification: f64,
+    pub inconvenience: f64,
+    pub hype: f64,
 }
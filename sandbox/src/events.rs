@@ -1,10 +1,9 @@
 use uuid::Uuid;
-use strum::Display;
-use std::string::ToString;
+use strum::{Display, IntoStaticStr};
 
-use crate::{bases::Baserunners, entities::{Player, World}, mods::{Mod, ModLifetime}, Game, Weather};
+use crate::{bases::{Advancement, Baserunners}, entities::{AttrCategory, Boosts, Player, World}, mods::Mod, game_end, rotation, Game};
 
-#[derive(Display, Debug, Clone)]
+#[derive(Display, IntoStaticStr, Debug, Clone)]
 pub enum Event {
     BatterUp {
         batter: Uuid
@@ -23,24 +22,29 @@ pub enum Event {
     Walk,
     HomeRun,
 
-    // todo: find a nicer way to encode runner advancement
+    //runners_after is where everyone ended up, scored is who crossed home getting there
     BaseHit {
         bases: u8,
         runners_after: Baserunners,
+        scored: Advancement,
     },
     GroundOut {
         fielder: Uuid,
         runners_after: Baserunners,
+        scored: Advancement,
     },
     Flyout {
         fielder: Uuid,
         runners_after: Baserunners,
+        scored: Advancement,
     },
     DoublePlay {
-        runners_after: Baserunners
+        runners_after: Baserunners,
+        scored: Advancement,
     },
     FieldersChoice {
-        runners_after: Baserunners
+        runners_after: Baserunners,
+        scored: Advancement,
     },
 
     BaseSteal {
@@ -54,7 +58,7 @@ pub enum Event {
     },
     Party {
         target: Uuid,
-        boosts: Vec<f64>
+        boosts: Boosts
     },
     Incineration {
         target: Uuid,
@@ -78,7 +82,7 @@ pub enum Event {
     Blooddrain {
         drainer: Uuid,
         target: Uuid,
-        stat: u8,
+        stat: AttrCategory,
         siphon: bool,
         siphon_effect: i16
     },
@@ -97,7 +101,7 @@ pub enum Event {
         batter: bool,
         replacement: Uuid,
         replacement_idx: usize,
-        boosts: Vec<f64>
+        boosts: Boosts
     },
     Fireproof {
         target: Uuid,
@@ -105,7 +109,7 @@ pub enum Event {
     Soundproof {
         resists: Uuid,
         tangled: Uuid,
-        decreases: Vec<f64>
+        decreases: Boosts
     },
     Reverberating {
         batter: Uuid,
@@ -184,10 +188,7 @@ pub enum Event {
 impl Event {
     pub fn apply(&self, game: &mut Game, world: &mut World) {
         let repr = self.repr();
-        if let Event::BatterUp { .. } = self {
-            assert_eq!(repr, String::from("BatterUp"));
-        }
-        game.events.add(repr.clone());
+        game.events.add(repr);
         match *self {
             Event::BatterUp { batter } => {
                 let bt = game.scoreboard.batting_team_mut();
@@ -195,17 +196,7 @@ impl Event {
                 if !game.started { game.started = true };
             }
             Event::InningSwitch { inning, top } => {
-                if let Weather::Salmon = game.weather {
-                    if game.scoreboard.top {
-                        let runs_away = game.scoreboard.away_team.score - game.linescore_away[0];
-                        game.linescore_away.push(runs_away);
-                        game.linescore_away[0] += runs_away;
-                    } else {
-                        let runs_home = game.scoreboard.home_team.score - game.linescore_home[0];
-                        game.linescore_home.push(runs_home);
-                        game.linescore_home[0] += runs_home;
-                    }
-                }
+                game.linescore.start(inning, top);
                 game.inning = inning;
                 game.scoreboard.top = top;
                 game.outs = 0;
@@ -215,8 +206,22 @@ impl Event {
                 game.runners = Baserunners::new(game.get_bases(world));
             }
             Event::GameOver => {
-                let winning_team = if game.scoreboard.home_team.score > game.scoreboard.away_team.score { game.scoreboard.home_team.id } else { game.scoreboard.away_team.id };
-                let losing_team = if game.scoreboard.home_team.score > game.scoreboard.away_team.score { game.scoreboard.away_team.id } else { game.scoreboard.home_team.id };
+                let (winning_team, losing_team) = game_end::result(game);
+                rotation::advance(world, game.scoreboard.home_team.id);
+                rotation::advance(world, game.scoreboard.away_team.id);
+                //whatever shame either team brought into this game has been paid off
+                world.team_mut(winning_team).shame_runs = 0.0;
+                world.team_mut(losing_team).shame_runs = 0.0;
+                if game_end::shamed(game, &world.ruleset) {
+                    game.shame = true;
+                    let shame_runs = game_end::shame_runs(game);
+                    world.team_mut(game.scoreboard.home_team.id).shamings += 1;
+                    let shamed = world.team_mut(game.scoreboard.away_team.id);
+                    shamed.shames += 1;
+                    if shamed.mods.has(Mod::TargetedShame) {
+                        shamed.shame_runs = shame_runs;
+                    }
+                }
                 if game.day < 99 {
                     world.team_mut(winning_team).wins += 1;
                     world.team_mut(losing_team).losses += 1;
@@ -236,13 +241,13 @@ impl Event {
                 game.strikes = game.strikes.min(game.get_max_strikes(world) - 1);
             }
             Event::Strikeout | Event::CharmStrikeout => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
                 let triple_threat_active = world.player(game.pitcher()).mods.has(Mod::TripleThreat)
                     && (game.balls == 3
                         || game.runners.occupied(2)
                         || game.runners.len() == 3);
                 if triple_threat_active {
-                    game.scoreboard.batting_team_mut().score -= 0.3;
+                    game.add_runs(-0.3);
                 }
                 game.outs += 1;
                 game.end_pa();
@@ -250,82 +255,77 @@ impl Event {
             Event::Walk | Event::CharmWalk => {
                 // maybe we should put batter in the event
                 // todo: make a function that returns the current batter
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
-                game.runners.walk();
-                game.runners.add(0, game.batter().unwrap());
-                game.score(world);
-                game.base_sweep();
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
+                let advancement = game.runners.force(0, game.batter().unwrap(), game.pitcher());
+                game.score(world, &advancement, game.batter());
                 game.end_pa();
             }
             Event::HomeRun => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
                 upgrade_spicy(game, world);
                 let no_runners_on = game.runners.empty();
-                game.runners.advance_all(game.get_bases(world));
-                game.score(world);
-                game.scoreboard.batting_team_mut().score += game.get_run_value();
-                game.scoreboard.batting_team_mut().score += world.player(game.batter().unwrap()).get_run_value();
-                game.base_sweep();
+                let advancement = game.runners.advance_all(game.get_bases(world));
+                game.score(world, &advancement, game.batter());
+                game.add_runs(game.get_run_value());
+                game.add_runs(world.player(game.batter().unwrap()).get_run_value());
+                game.linescore.add_hit(game.inning, game.scoreboard.top);
+                game.record_run(world, game.batter().unwrap(), game.batter(), game.pitcher());
                 if no_runners_on {
                     game.scoring_plays_inning += 1;
                 } //this is to make sum sun not break
                 game.end_pa();
             }
             Event::BaseHit {
-                bases,
+                bases: _bases,
                 ref runners_after,
+                ref scored,
             } => {
-                let batter = game.batter().unwrap();
-                world.player_mut(batter).feed.add(repr.clone());
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
                 upgrade_spicy(game, world);
+                game.linescore.add_hit(game.inning, game.scoreboard.top);
                 game.runners = runners_after.clone();
-                game.score(world);
-                game.base_sweep();
-                game.runners
-                    .add(bases - 1, batter);
+                game.score(world, scored, game.batter());
                 game.end_pa();
             }
             Event::GroundOut {
                 fielder: _fielder,
                 ref runners_after,
+                ref scored,
             } => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
                 downgrade_spicy(game, world);
                 game.outs += 1;
                 game.runners = runners_after.clone();
-                game.score(world);
-                game.base_sweep();
+                game.score(world, scored, game.batter());
                 game.end_pa();
             }
             Event::Flyout {
                 fielder: _fielder,
                 ref runners_after,
+                ref scored,
             } => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
                 downgrade_spicy(game, world);
                 game.outs += 1;
                 game.runners = runners_after.clone();
-                game.score(world);
-                game.base_sweep();
+                game.score(world, scored, game.batter());
                 game.end_pa();
             }
-            Event::DoublePlay { ref runners_after } => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
+            Event::DoublePlay { ref runners_after, ref scored } => {
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
                 downgrade_spicy(game, world);
                 game.outs += 2;
                 game.runners = runners_after.clone();
-                game.score(world);
-                game.base_sweep();
+                game.score(world, scored, None);
                 game.end_pa();
             }
-            Event::FieldersChoice { ref runners_after } => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
+            Event::FieldersChoice { ref runners_after, ref scored } => {
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
                 downgrade_spicy(game, world);
                 game.outs += 1;
                 game.runners = runners_after.clone();
-                game.runners.add(0, game.batter().unwrap());
-                game.score(world);
-                game.base_sweep();
+                game.runners.add(0, game.batter().unwrap(), game.pitcher());
+                game.score(world, scored, game.batter());
                 game.end_pa();
             }
             Event::BaseSteal {
@@ -334,10 +334,10 @@ impl Event {
                 base_to: _base_to,
             } => {
                 if world.player(runner).mods.has(Mod::Blaserunning) {
-                    game.scoreboard.batting_team_mut().score += 0.2;
+                    game.add_runs(0.2);
                 }
-                game.runners.advance(base_from);
-                game.base_sweep();
+                let advancement = game.runners.advance(base_from);
+                game.score(world, &advancement, None);
             }
             Event::CaughtStealing {
                 runner: _runner,
@@ -356,7 +356,7 @@ impl Event {
                 println!("{} at {}, day {}", world.team(game.scoreboard.away_team.id).name, world.team(game.scoreboard.home_team.id).name, game.day);
                 println!("Incineration: {}", world.player(target).name);
                 println!("Team: {}", world.team(world.player(target).team.unwrap()).name);
-                let new_player = replacement.name == "";
+                let new_player = !world.players.contains_key(&replacement.id);
                 let replacement_id = if new_player {
                     world.add_rolled_player(replacement.clone(), world.player(target).team.unwrap())
                 } else {
@@ -377,7 +377,7 @@ impl Event {
                     world.swap_hall(target, replacement_id);
                 }
                 if chain.is_some() {
-                    world.player_mut(chain.unwrap()).mods.add(Mod::Unstable, ModLifetime::Week);
+                    world.player_mut(chain.unwrap()).mods.add_default(Mod::Unstable);
                 }
             },
             Event::Peanut { target, yummy } => {
@@ -389,9 +389,7 @@ impl Event {
                 } else {
                     -0.2
                 };
-                let boosts: Vec<f64> = vec![coeff; 26];
-                let player = world.player_mut(target);
-                player.boost(&boosts);
+                world.player_mut(target).boost(&Boosts::all(coeff));
             },
             Event::Birds => {},
             Event::Feedback { target1, target2 } => {
@@ -421,9 +419,9 @@ impl Event {
                     game.assign_batter(new_batter);
                 } else if reverb_type != 2 {
                     if game.scoreboard.pitching_team().id == team {
-                        game.assign_pitcher(world.team(team).rotation[game.day % world.team(team).rotation.len()].clone());
+                        game.assign_pitcher(rotation::starter(world, team));
                     } else {
-                        game.scoreboard.batting_team_mut().pitcher = world.team(team).rotation[game.day % world.team(team).rotation.len()].clone();
+                        game.scoreboard.batting_team_mut().pitcher = rotation::starter(world, team);
                     }
                 }
             },
@@ -433,37 +431,7 @@ impl Event {
                 println!("Drainer team: {}", world.team(world.player(drainer).team.unwrap()).name);
                 match siphon_effect {
                     -1 => {
-                        let drainer_mut = world.player_mut(drainer);
-                        let mut boosts: Vec<f64> = vec![0.0; 26];
-                        match stat {
-                            0 => {
-                                //pitching
-                                for i in 8..14 {
-                                    boosts[i] = 0.1;
-                                }
-                            },
-                            1 => {
-                                //batting
-                                for i in 0..8 {
-                                    boosts[i] = 0.1;
-                                }
-                            },
-                            2 => {
-                                //defense
-                                for i in 19..24 {
-                                    boosts[i] = 0.1;
-                                }
-                            },
-                            3 => {
-                                //baserunning
-                                for i in 14..19 {
-                                    boosts[i] = 0.1;
-                                }
-                            },
-                            _ => {
-                            }
-                        }
-                        drainer_mut.boost(&boosts);
+                        world.player_mut(drainer).boost(&Boosts::category(stat, 0.1));
                     },
                     0 => {
                         game.outs += 1;
@@ -479,33 +447,7 @@ impl Event {
                     }
                 }
 
-                let target_mut = world.player_mut(target);
-                let mut decreases: Vec<f64> = vec![0.0; 26];
-                match stat {
-                    0 => {
-                        for i in 8..14 {
-                            decreases[i] = -0.1;
-                        }
-                    },
-                    1 => {
-                        for i in 0..8 {
-                            decreases[i] = -0.1;
-                        }
-                    },
-                    2 => {
-                        for i in 19..24 {
-                            decreases[i] = -0.1;
-                        }
-                    },
-                    3 => {
-                        for i in 14..19 {
-                            decreases[i] = -0.1;
-                        }
-                    },
-                    _ => {
-                    }
-                }
-                target_mut.boost(&decreases);
+                world.player_mut(target).boost(&Boosts::category(stat, -0.1));
             },
             //todo: add win manipulation when we actually have wins
             Event::Sun2 { home_team } => {
@@ -543,23 +485,17 @@ impl Event {
                 }
             },
             Event::Salmon { home_runs_lost, away_runs_lost } => {
-                if !game.events.has(String::from("Salmon"), if game.scoreboard.top { 3 } else { 2 }) {
-                    game.salmon_resets_inning = 0;
+                //back to the top of the inning that just finished, which is this one if only the top did
+                if game.scoreboard.top {
+                    game.inning -= 1;
                 }
+                game.scoreboard.top = true;
                 if away_runs_lost {
-                    //this whole exercise's goal is
-                    //to find the first instance of the inning
-                    game.scoreboard.away_team.score -= game.linescore_away[game.linescore_away.len() - 1 - (game.salmon_resets_inning as usize)];
+                    game.scoreboard.away_team.score -= game.linescore.rewind(game.inning, true);
                 }
                 if home_runs_lost {
-                    game.scoreboard.home_team.score -= game.linescore_home[game.linescore_home.len() - 1 - (game.salmon_resets_inning as usize)];
+                    game.scoreboard.home_team.score -= game.linescore.rewind(game.inning, false);
                 }
-                if !game.scoreboard.top {
-                    game.scoreboard.top = true
-                } else {
-                    game.inning -= 1;
-                }
-                game.salmon_resets_inning += 1;
             },
             Event::PolaritySwitch => {
                 game.polarity = !game.polarity;
@@ -577,8 +513,7 @@ impl Event {
                 } else {
                     let team = game.scoreboard.pitching_team();
                     let active_pitcher = team.pitcher;
-                    let active_pitcher_idx = 0; //todo: this only works for one game
-                    world.team_mut(team.id).rotation[active_pitcher_idx] = replacement;
+                    rotation::replace(world.team_mut(team.id), active_pitcher, replacement);
                     world.team_mut(team.id).shadows[replacement_idx] = active_pitcher;
                     world.player_mut(replacement).boost(boosts);
                     let team_mut = game.scoreboard.pitching_team_mut();
@@ -606,16 +541,14 @@ impl Event {
                     2 => Some(Mod::Repeating),
                     _ => None
                 };
-                world.player_mut(target).mods.add(effect.unwrap(), ModLifetime::Week);
-                game.runners.walk();
-                game.runners.add(0, game.batter().unwrap());
-                game.score(world);
-                game.base_sweep();
+                world.player_mut(target).mods.add_default(effect.unwrap());
+                let advancement = game.runners.force(0, game.batter().unwrap(), game.pitcher());
+                game.score(world, &advancement, game.batter());
                 game.end_pa();
             },
             Event::PeckedFree { player } => {
                 world.player_mut(player).mods.remove(Mod::Shelled);
-                world.player_mut(player).mods.add(Mod::Superallergic, ModLifetime::Permanent);
+                world.player_mut(player).mods.add_default(Mod::Superallergic);
             },
             Event::Zap { batter } => {
                 if batter {
@@ -625,31 +558,27 @@ impl Event {
                 }
             },
             Event::InstinctWalk { third } => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
-                game.runners.walk_instincts(third);
-                game.runners.add(if third { 2 } else { 1 }, game.batter().unwrap());
-                game.score(world);
-                game.base_sweep();
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
+                let advancement = game.runners.force(if third { 2 } else { 1 }, game.batter().unwrap(), game.pitcher());
+                game.score(world, &advancement, game.batter());
                 game.end_pa();
             },
             Event::BigPeanut { target } => {
                 println!("{} at {}, day {}", world.team(game.scoreboard.away_team.id).name, world.team(game.scoreboard.home_team.id).name, game.day);
                 println!("Shelled by big peanut: {}", world.player(target).name);
                 println!("Team: {}", world.team(world.player(target).team.unwrap()).name);
-                world.player_mut(target).mods.add(Mod::Shelled, ModLifetime::Permanent);
+                world.player_mut(target).mods.add_default(Mod::Shelled);
             },
             Event::MildPitch => {
                 game.balls += 1;
-                game.runners.advance_all(1);
-                game.score(world);
-                game.base_sweep();
+                let advancement = game.runners.advance_all(1);
+                game.score(world, &advancement, None);
             },
             Event::MildWalk => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
-                game.runners.advance_all(1);
-                game.runners.add(0, game.batter().unwrap());
-                game.score(world);
-                game.base_sweep();
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
+                let advancement = game.runners.advance_all(1);
+                game.runners.add(0, game.batter().unwrap(), game.pitcher());
+                game.score(world, &advancement, game.batter());
                 game.end_pa();
             },
             Event::Repeating { batter } => {
@@ -658,18 +587,19 @@ impl Event {
                 bt.batter = Some(batter);
             },
             Event::FireEater { target } => {
-                world.player_mut(target).mods.add(Mod::Magmatic, ModLifetime::Permanent);
+                world.player_mut(target).mods.add_default(Mod::Magmatic);
             },
             Event::MagmaticHomeRun => {
-                world.player_mut(game.batter().unwrap()).feed.add(repr.clone());
+                world.player_mut(game.batter().unwrap()).feed.add(repr);
                 world.player_mut(game.batter().unwrap()).mods.remove(Mod::Magmatic);
                 upgrade_spicy(game, world);
                 let no_runners_on = game.runners.empty();
-                game.runners.advance_all(game.get_bases(world));
-                game.score(world);
-                game.scoreboard.batting_team_mut().score += game.get_run_value();
-                game.scoreboard.batting_team_mut().score += world.player(game.batter().unwrap()).get_run_value();
-                game.base_sweep();
+                let advancement = game.runners.advance_all(game.get_bases(world));
+                game.score(world, &advancement, game.batter());
+                game.add_runs(game.get_run_value());
+                game.add_runs(world.player(game.batter().unwrap()).get_run_value());
+                game.linescore.add_hit(game.inning, game.scoreboard.top);
+                game.record_run(world, game.batter().unwrap(), game.batter(), game.pitcher());
                 if no_runners_on {
                     game.scoring_plays_inning += 1;
                 } //this is to make sum sun not break
@@ -680,7 +610,7 @@ impl Event {
                 game.end_pa();
             },
             Event::TasteTheInfinite { target } => {
-                world.player_mut(target).mods.add(Mod::Shelled, ModLifetime::Permanent);
+                world.player_mut(target).mods.add_default(Mod::Shelled);
             },
             Event::Inhabiting { batter: _batter, inhabit } => {
                 let bt = game.scoreboard.batting_team_mut();
@@ -690,29 +620,28 @@ impl Event {
             Event::BlockedDrain { drainer: _drainer, target: _target } => {},
             Event::Performing { ref overperforming, ref underperforming } => {
                 for &player in overperforming {
-                    world.player_mut(player).mods.add(Mod::Overperforming, ModLifetime::Game);
+                    world.player_mut(player).mods.add_default(Mod::Overperforming);
                 }
                 for &player in underperforming {
-                    world.player_mut(player).mods.add(Mod::Underperforming, ModLifetime::Game);
+                    world.player_mut(player).mods.add_default(Mod::Underperforming);
                 }
             },
             Event::Beaned => {
                 let batter = world.player_mut(game.batter().unwrap());
                 if batter.mods.has(Mod::Wired) {
-                    batter.mods.remove(Mod::Wired);
-                    batter.mods.add(Mod::Tired, ModLifetime::Game);
+                    batter.mods.add_default(Mod::Tired);
                 } else if batter.mods.has(Mod::Tired) {
                     batter.mods.remove(Mod::Tired);
                 } else {
-                    batter.mods.add(Mod::Wired, ModLifetime::Game);
+                    batter.mods.add_default(Mod::Wired);
                 }
             },
             Event::PouredOver => {
-                world.player_mut(game.batter().unwrap()).mods.add(Mod::FreeRefill, ModLifetime::Game);
+                world.player_mut(game.batter().unwrap()).mods.add_default(Mod::FreeRefill);
             },
             Event::TripleThreat => {
-                world.player_mut(game.scoreboard.home_team.pitcher).mods.add(Mod::TripleThreat, ModLifetime::Permanent);
-                world.player_mut(game.scoreboard.away_team.pitcher).mods.add(Mod::TripleThreat, ModLifetime::Permanent);
+                world.player_mut(game.scoreboard.home_team.pitcher).mods.add_default(Mod::TripleThreat);
+                world.player_mut(game.scoreboard.away_team.pitcher).mods.add_default(Mod::TripleThreat);
             },
             Event::TripleThreatDeactivation { home, away } => {
                 if home { world.player_mut(game.scoreboard.home_team.pitcher).mods.remove(Mod::TripleThreat); }
@@ -724,7 +653,7 @@ impl Event {
                     println!("{} at {}, day {}", world.team(game.scoreboard.away_team.id).name, world.team(game.scoreboard.home_team.id).name, game.day);
                     println!("Swept Elsewhere: {}", world.player(runner).name);
                     println!("Team: {}", world.team(world.player(runner).team.unwrap()).name);
-                    world.player_mut(runner).mods.add(Mod::Elsewhere, ModLifetime::Permanent);
+                    world.player_mut(runner).mods.add_default(Mod::Elsewhere);
                     world.player_mut(runner).swept_on = Some(game.day);
                 }
             },
@@ -740,7 +669,7 @@ impl Event {
                     let player = returned[i];
                     println!("Scattered: {}, {} letters", world.player(player).name, letters[i]);
                     if letters[i] > 0 {
-                        world.player_mut(player).mods.add(Mod::Scattered, ModLifetime::Permanent);
+                        world.player_mut(player).mods.add_default(Mod::Scattered);
                         world.player_mut(player).scattered_letters = letters[i];
                     }
                 }
@@ -757,23 +686,19 @@ impl Event {
         }
     }
 
-    //todo: might merge this with a possible future print function
-    //btw these don't need to be growable but static lifetimes
-    //are annoying
-    fn repr(&self) -> String {
-        let ev = self.to_string();
-        String::from(ev)
+    //the variant name, which is what the event logs keep
+    fn repr(&self) -> &'static str {
+        self.into()
     }
 }
 
 
 fn upgrade_spicy(game: &mut Game, world: &mut World) {
     let batter = world.player_mut(game.batter().unwrap());
-    if batter.mods.has(Mod::Spicy) && batter.feed.streak_multiple(vec![String::from("BaseHit"), String::from("HomeRun")], -1) == 1 {
-        batter.mods.add(Mod::HeatingUp, ModLifetime::Permanent);
+    if batter.mods.has(Mod::Spicy) && batter.feed.streak_multiple(&["BaseHit", "HomeRun"], -1) == 1 {
+        batter.mods.add_default(Mod::HeatingUp);
     } else if batter.mods.has(Mod::HeatingUp) {
-        batter.mods.remove(Mod::HeatingUp);
-        batter.mods.add(Mod::RedHot, ModLifetime::Permanent);
+        batter.mods.add_default(Mod::RedHot);
     }
 }
 
@@ -788,7 +713,7 @@ fn downgrade_spicy(game: &mut Game, world: &mut World) {
 
 #[derive(Clone, Debug)]
 pub struct Events {
-    events: Vec<String>
+    events: Vec<&'static str>
 }
 
 impl Events {
@@ -797,19 +722,22 @@ impl Events {
             events: Vec::new()
         }
     }
-    pub fn add(&mut self, repr: String) {
+    pub fn add(&mut self, repr: &'static str) {
         self.events.push(repr);
     }
     pub fn len(&self) -> usize {
         self.events.len()
     }
-    pub fn last(&self) -> &String {
+    pub fn get(&self, idx: usize) -> &'static str {
+        self.events[idx]
+    }
+    pub fn last(&self) -> &'static str {
         if self.events.len() == 0 {
             panic!("don't call this when the game begins");
         }
         self.events.last().unwrap()
     }
-    pub fn has(&self, s: String, limit: i16) -> bool {
+    pub fn has(&self, s: &str, limit: i16) -> bool {
         let mut half_innings = 0i16;
         for ev in self.events.iter().rev() {
             if *ev == s {
@@ -824,7 +752,7 @@ impl Events {
         }
         false
     }
-    pub fn count(&self, s: String, limit: i16) -> u8 {
+    pub fn count(&self, s: &str, limit: i16) -> u8 {
         let mut half_innings = 0i16;
         let mut counter = 0u8;
         for ev in self.events.iter().rev() {
@@ -840,7 +768,7 @@ impl Events {
         }
         counter
     }
-    pub fn streak_multiple(&self, strvec: Vec<String>, limit: i16) -> u8 {
+    pub fn streak_multiple(&self, strs: &[&str], limit: i16) -> u8 {
         let mut half_innings = 0i16;
         let mut counter = 0u8;
         for ev in self.events.iter().rev() {
@@ -852,7 +780,7 @@ impl Events {
                 }
             } else {
 		//contains doesn't work
-		for s in &strvec {
+		for s in strs {
 		    if *ev == *s {
 			counter += 1;
 		    }
@@ -0,0 +1,245 @@
+use std::fmt;
+
+use crate::entities::{Player, PlayerAttr};
+use crate::formulas::{self, coeff, item, multiplier};
+use crate::ruleset::Ruleset;
+use crate::MultiplierData;
+
+//breaking thresholds down into the stats that go into them,
+//and how much each stat moves them
+
+//how far stats get nudged for the derivatives
+const STEP: f64 = 0.001;
+//values a stat gets set to, to tell one that's clamped off from one the threshold doesn't use
+const PROBES: [f64; 5] = [-1.0, 0.0, 0.5, 1.0, 2.0];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Pitcher,
+    Batter,
+    Fielder,
+    Runner,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [Role::Pitcher, Role::Batter, Role::Fielder, Role::Runner];
+
+    //whose team mods the multiplier looks at
+    pub fn batting_team(&self) -> bool {
+        matches!(self, Role::Batter | Role::Runner)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Threshold {
+    Strike,
+    FlinchStrike,
+    SwingOnStrike,
+    SwingOnBall,
+    ContactOnStrike,
+    ContactOnBall,
+    Foul,
+    Out,
+    Fly,
+    HomeRun,
+    Quadruple,
+    Triple,
+    Double,
+    HitAdvancement,
+    GroundoutSacrifice,
+    GroundoutAdvancement,
+    DoublePlay,
+    FlyoutAdvancement(u8), //base the runner starts from
+}
+
+impl Threshold {
+    pub const ALL: [Threshold; 20] = [
+        Threshold::Strike,
+        Threshold::FlinchStrike,
+        Threshold::SwingOnStrike,
+        Threshold::SwingOnBall,
+        Threshold::ContactOnStrike,
+        Threshold::ContactOnBall,
+        Threshold::Foul,
+        Threshold::Out,
+        Threshold::Fly,
+        Threshold::HomeRun,
+        Threshold::Quadruple,
+        Threshold::Triple,
+        Threshold::Double,
+        Threshold::HitAdvancement,
+        Threshold::GroundoutSacrifice,
+        Threshold::GroundoutAdvancement,
+        Threshold::DoublePlay,
+        Threshold::FlyoutAdvancement(0),
+        Threshold::FlyoutAdvancement(1),
+        Threshold::FlyoutAdvancement(2),
+    ];
+}
+
+//everyone who could be involved in a threshold. runner and fielder can be anyone for thresholds that don't use them
+#[derive(Clone, Copy)]
+pub struct Matchup<'a> {
+    pub pitcher: &'a Player,
+    pub batter: &'a Player,
+    pub fielder: &'a Player,
+    pub runner: &'a Player,
+    pub ruleset: &'a Ruleset,
+    pub multiplier_data: &'a MultiplierData<'a>,
+}
+
+impl<'a> Matchup<'a> {
+    pub fn player(&self, role: Role) -> &'a Player {
+        match role {
+            Role::Pitcher => self.pitcher,
+            Role::Batter => self.batter,
+            Role::Fielder => self.fielder,
+            Role::Runner => self.runner,
+        }
+    }
+
+    fn with(&self, role: Role, player: &'a Player) -> Matchup<'a> {
+        let mut matchup = *self;
+        match role {
+            Role::Pitcher => matchup.pitcher = player,
+            Role::Batter => matchup.batter = player,
+            Role::Fielder => matchup.fielder = player,
+            Role::Runner => matchup.runner = player,
+        }
+        matchup
+    }
+
+    pub fn threshold(&self, threshold: Threshold) -> f64 {
+        let (pitcher, batter, fielder, runner, ruleset, data) = (self.pitcher, self.batter, self.fielder, self.runner, self.ruleset, self.multiplier_data);
+        match threshold {
+            Threshold::Strike => formulas::strike_threshold(pitcher, batter, false, ruleset, data),
+            Threshold::FlinchStrike => formulas::strike_threshold(pitcher, batter, true, ruleset, data),
+            Threshold::SwingOnStrike => formulas::swing_threshold(pitcher, batter, true, ruleset, data),
+            Threshold::SwingOnBall => formulas::swing_threshold(pitcher, batter, false, ruleset, data),
+            Threshold::ContactOnStrike => formulas::contact_threshold(pitcher, batter, true, ruleset, data),
+            Threshold::ContactOnBall => formulas::contact_threshold(pitcher, batter, false, ruleset, data),
+            Threshold::Foul => formulas::foul_threshold(pitcher, batter, ruleset, data),
+            Threshold::Out => formulas::out_threshold(pitcher, batter, fielder, ruleset, data),
+            Threshold::Fly => formulas::fly_threshold(batter, pitcher, ruleset, data),
+            Threshold::HomeRun => formulas::hr_threshold(pitcher, batter, ruleset, data),
+            Threshold::Quadruple => formulas::quadruple_threshold(pitcher, batter, fielder, ruleset, data),
+            Threshold::Triple => formulas::triple_threshold(pitcher, batter, fielder, ruleset, data),
+            Threshold::Double => formulas::double_threshold(pitcher, batter, fielder, ruleset, data),
+            Threshold::HitAdvancement => formulas::hit_advancement_threshold(runner, fielder, ruleset, data),
+            Threshold::GroundoutSacrifice => formulas::groundout_sacrifice_threshold(batter, ruleset, data),
+            Threshold::GroundoutAdvancement => formulas::groundout_advancement_threshold(runner, fielder, ruleset, data),
+            Threshold::DoublePlay => formulas::double_play_threshold(batter, pitcher, fielder, ruleset, data),
+            Threshold::FlyoutAdvancement(base) => formulas::flyout_advancement_threshold(runner, base, ruleset, data),
+        }
+    }
+
+    //how much the threshold changes if `role`'s `attr` goes up by `amount`
+    pub fn delta(&self, threshold: Threshold, role: Role, attr: PlayerAttr, amount: f64) -> f64 {
+        let mut changed = self.player(role).clone();
+        changed.set(attr, changed.get(attr) + amount);
+        self.with(role, &changed).threshold(threshold) - self.threshold(threshold)
+    }
+
+    //central difference, so kinks from clamping show up as the average of both sides
+    pub fn derivative(&self, threshold: Threshold, role: Role, attr: PlayerAttr) -> f64 {
+        let player = self.player(role);
+        let mut up = player.clone();
+        up.set(attr, player.get(attr) + STEP);
+        let mut down = player.clone();
+        down.set(attr, player.get(attr) - STEP);
+        (self.with(role, &up).threshold(threshold) - self.with(role, &down).threshold(threshold)) / (2.0 * STEP)
+    }
+
+    //whether the threshold moves at all with the stat set anywhere in PROBES, nudging or not
+    pub fn uses(&self, threshold: Threshold, role: Role, attr: PlayerAttr) -> bool {
+        let value = self.threshold(threshold);
+        PROBES.iter().any(|&probe| {
+            let mut changed = self.player(role).clone();
+            changed.set(attr, probe);
+            (self.with(role, &changed).threshold(threshold) - value).abs() > 1e-9
+        })
+    }
+
+    //every stat the threshold uses, including the ones a clamp keeps from mattering right now
+    pub fn explain(&self, threshold: Threshold) -> Explanation {
+        let mut stats = Vec::new();
+        let mut sensitivities = Vec::new();
+        for role in Role::ALL {
+            for attr in PlayerAttr::ALL {
+                let derivative = self.derivative(threshold, role, attr);
+                let clamped = derivative.abs() <= 1e-9 && self.uses(threshold, role, attr);
+                if derivative.abs() > 1e-9 || clamped {
+                    stats.push(breakdown(attr, role, self.player(role), self.multiplier_data));
+                    sensitivities.push(Sensitivity { role, attr, derivative, clamped });
+                }
+            }
+        }
+        Explanation {
+            threshold,
+            value: self.threshold(threshold),
+            stats,
+            sensitivities,
+        }
+    }
+}
+
+//the pieces `coeff` puts together, plus the vibes factor most formulas multiply on top
+//not every formula applies vibes to every stat, the derivatives are what to trust for that
+#[derive(Clone, Debug)]
+pub struct StatBreakdown {
+    pub role: Role,
+    pub attr: PlayerAttr,
+    pub raw: f64,
+    pub item: f64,
+    pub multiplier: f64,
+    pub vibes: f64,
+    pub coefficient: f64,
+}
+
+pub fn breakdown(attr: PlayerAttr, role: Role, player: &Player, multiplier_data: &MultiplierData) -> StatBreakdown {
+    let raw = player.get(attr);
+    StatBreakdown {
+        role,
+        attr,
+        raw,
+        item: item(attr, &player.legendary_item),
+        multiplier: multiplier(attr, &player.mods, multiplier_data, role.batting_team()),
+        vibes: 1.0 + 0.2 * player.vibes(multiplier_data.day),
+        coefficient: coeff(attr, &player.legendary_item, &player.mods, multiplier_data, role.batting_team(), raw),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Sensitivity {
+    pub role: Role,
+    pub attr: PlayerAttr,
+    pub derivative: f64,
+    pub clamped: bool, //the threshold uses the stat but sits at one of its limits, so the derivative is 0
+}
+
+#[derive(Clone, Debug)]
+pub struct Explanation {
+    pub threshold: Threshold,
+    pub value: f64,
+    pub stats: Vec<StatBreakdown>,
+    pub sensitivities: Vec<Sensitivity>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?}: {:.4}", self.threshold, self.value)?;
+        for (stat, sensitivity) in self.stats.iter().zip(self.sensitivities.iter()) {
+            let effect = if sensitivity.clamped {
+                "clamped".to_string()
+            } else {
+                format!("d/dstat {:+.4} (+0.1: {:+.4})", sensitivity.derivative, 0.1 * sensitivity.derivative)
+            };
+            writeln!(
+                f,
+                "    {:?} {:?}: raw {:.3}, item {:+.3}, multiplier {:.3}, vibes {:.3}, coefficient {:.3}, {}",
+                stat.role, stat.attr, stat.raw, stat.item, stat.multiplier, stat.vibes, stat.coefficient, effect
+            )?;
+        }
+        Ok(())
+    }
+}
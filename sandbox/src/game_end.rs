@@ -0,0 +1,103 @@
+use uuid::Uuid;
+
+use crate::{ruleset::Ruleset, Game, Weather};
+
+//when games end. scores are floats that move in tenths (or less, with sun point one), so they're
+//compared with some slack, and runs can be worth less than nothing, so nothing here assumes a score only goes up
+
+//scores closer than this are tied
+pub const TIE: f64 = 0.01;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Leader {
+    Away,
+    Home,
+    Tied,
+}
+
+pub fn leader(away: f64, home: f64) -> Leader {
+    if (away - home).abs() < TIE {
+        Leader::Tied
+    } else if away > home {
+        Leader::Away
+    } else {
+        Leader::Home
+    }
+}
+
+//sun 2 and black holes go off for a team at 10 runs
+pub fn reached_ten(score: f64) -> bool {
+    score > 9.99 //ugh
+}
+
+//sun 2 and black holes take their 10 runs on the tick after the play that got a team there,
+//so until then the score isn't what it's about to be
+pub fn weather_pending(game: &Game) -> bool {
+    matches!(game.weather, Weather::Sun2 | Weather::BlackHole)
+        && (reached_ten(game.scoreboard.away_team.score) || reached_ten(game.scoreboard.home_team.score))
+}
+
+//(away, home) once the weather's taken its runs
+pub fn settled_scores(game: &Game) -> (f64, f64) {
+    let settle = |mut score: f64| {
+        if matches!(game.weather, Weather::Sun2 | Weather::BlackHole) {
+            while reached_ten(score) {
+                score -= 10.0;
+            }
+        }
+        score
+    };
+    (settle(game.scoreboard.away_team.score), settle(game.scoreboard.home_team.score))
+}
+
+//the home team taking the lead in the bottom of the last inning or an extra one ends the game on the spot,
+//in rulesets with walk-offs. it waits for the weather, since sun 2 or a black hole can take the lead right back
+pub fn walk_off(game: &Game, ruleset: &Ruleset) -> bool {
+    ruleset.walk_offs
+        && !game.scoreboard.top
+        && game.inning >= ruleset.innings
+        && !weather_pending(game)
+        && leader(game.scoreboard.away_team.score, game.scoreboard.home_team.score) == Leader::Home
+}
+
+//after the third out from the last inning on: the home team leading after the top doesn't need to bat,
+//and after the bottom anything but a tie is final. ties go to extra innings, for as many as it takes.
+//salmon rewinds happen after the inning switch, so a game that's over here never gets rewound
+pub fn over_after_half(game: &Game, ruleset: &Ruleset) -> bool {
+    if game.inning < ruleset.innings {
+        return false;
+    }
+    let (away, home) = settled_scores(game);
+    match leader(away, home) {
+        Leader::Home => true,
+        Leader::Away => !game.scoreboard.top,
+        Leader::Tied => false,
+    }
+}
+
+pub fn is_over(game: &Game, ruleset: &Ruleset) -> bool {
+    walk_off(game, ruleset) || game.outs >= 3 && over_after_half(game, ruleset)
+}
+
+//the game ending before the home team bats in the last inning. the away team is shamed,
+//by however many runs they were behind
+pub fn shamed(game: &Game, ruleset: &Ruleset) -> bool {
+    ruleset.shame && game.scoreboard.top && game.outs >= 3 && over_after_half(game, ruleset)
+}
+
+pub fn shame_runs(game: &Game) -> f64 {
+    let (away, home) = settled_scores(game);
+    home - away
+}
+
+//(winner, loser). a tie can't make it through is_over, but if one did it would go to the away team
+pub fn result(game: &Game) -> (Uuid, Uuid) {
+    let (away, home) = settled_scores(game);
+    let home_team = game.scoreboard.home_team.id;
+    let away_team = game.scoreboard.away_team.id;
+    if leader(away, home) == Leader::Home {
+        (home_team, away_team)
+    } else {
+        (away_team, home_team)
+    }
+}
@@ -1,17 +1,30 @@
-use bases::Baserunners;
-use entities::World;
+use bases::{Advancement, Baserunners};
+use entities::{Stadium, World};
 use mods::{Mod, Mods};
-use rng::Rng;
+use rng::Roll;
+use ruleset::{Era, Ruleset};
 use uuid::Uuid;
 use events::Events;
+use linescore::Linescore;
 
 pub mod bases;
+pub mod constants;
 pub mod entities;
 pub mod formulas;
+pub mod game_end;
+pub mod linescore;
+pub mod markov;
 pub mod mods;
+pub mod multipliers;
+pub mod odds;
+pub mod plate_appearance;
+pub mod ratings;
 pub mod rng;
+pub mod rotation;
+pub mod ruleset;
 pub mod sim;
 pub mod events;
+pub mod explain;
 
 #[derive(Clone, Debug)]
 pub enum Weather {
@@ -39,24 +52,36 @@ pub enum Weather {
 }
 
 impl Weather {
-    pub fn generate(rng: &mut Rng, season_ruleset: u8, day: usize) -> Weather {
+    //weather ids as they appear in game data
+    pub fn from_id(id: u8) -> Option<Weather> {
+        match id {
+            1 => Some(Weather::Sun2),
+            7 => Some(Weather::Eclipse),
+            9 => Some(Weather::Blooddrain),
+            10 => Some(Weather::Peanuts),
+            11 => Some(Weather::Birds),
+            12 => Some(Weather::Feedback),
+            13 => Some(Weather::Reverb),
+            14 => Some(Weather::BlackHole),
+            15 => Some(Weather::Coffee),
+            16 => Some(Weather::Coffee2),
+            17 => Some(Weather::Coffee3),
+            18 => Some(Weather::Flooding),
+            19 => Some(Weather::Salmon),
+            20 => Some(Weather::PolarityPlus),
+            21 => Some(Weather::PolarityMinus),
+            24 => Some(Weather::SunPointOne),
+            25 => Some(Weather::SumSun),
+            29 => Some(Weather::Night),
+            //todo: glitter, jazz and the weathers that don't do anything
+            _ => None
+        }
+    }
+
+    pub fn generate(rng: &mut impl Roll, ruleset: &Ruleset, day: usize) -> Weather {
         //todo: actually implement this
-        let weights = match season_ruleset {
-            11 => {
-                if day < 72 {
-                    vec![50, 20, 20, 35, 20, 20, 20, 50, 2, 2, 1] //todo: idk this just feels wrong
-                } else {
-                    vec![50, 20, 20, 35, 20, 20, 20, 50, 2, 2, 1, 200]
-                }
-            },
-            _ => todo!(),
-        };
-        let weight_sum = match season_ruleset {
-            11 => {
-                if day < 72 { 240 } else { 440 }
-            }
-            _ => todo!(),
-        };
+        let weights = ruleset.weather_weights(day);
+        let weight_sum = weights.iter().sum();
         let weathers = [
             Weather::Sun2, 
             Weather::Eclipse, 
@@ -103,7 +128,6 @@ pub struct Game {
 
     pub polarity: bool, //false for positive, true for negative
     pub scoring_plays_inning: u8,
-    pub salmon_resets_inning: i16,
 
     pub events: Events,
     pub started: bool,
@@ -111,9 +135,23 @@ pub struct Game {
     pub scoreboard: Scoreboard,
 
     pub runners: Baserunners,
+    pub runs: Vec<RunRecord>, //every run scored so far, in order
+
+    pub linescore: Linescore,
+
+    pub shame: bool, //set on game over, see game_end::shamed
+}
 
-    pub linescore_home: Vec<f64>, //for salmon purposes
-    pub linescore_away: Vec<f64>, //the first element is the total score
+//one run, for stats, box scores and play-by-play
+#[derive(Clone, Debug)]
+pub struct RunRecord {
+    pub runner: Uuid,
+    pub batter: Option<Uuid>, //the batter credited with the rbi, if anyone drove the run in
+    pub pitcher: Uuid, //whoever put the runner on, not necessarily who's pitching now
+    pub value: f64, //with Wired/Tired and the weather, what the run added to the score
+    pub event: usize, //the play that scored it, as an index into the game's events
+    pub inning: i16,
+    pub top: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -133,15 +171,18 @@ pub struct GameTeam {
 }
 
 //stealing this from Astrid
-pub struct MultiplierData {
-    batting_team_mods: Mods,
-    pitching_team_mods: Mods,
+pub struct MultiplierData<'a> {
+    batting_team_mods: &'a Mods,
+    pitching_team_mods: &'a Mods,
     weather: Weather,
     day: usize,
     runners_empty: bool,
     top: bool,
     maximum_blaseball: bool,
-    at_bats: i32
+    at_bats: i32,
+    era: Era,
+    //the home team's, if they have one
+    stadium: Option<&'a Stadium>,
 }
 
 
@@ -150,10 +191,12 @@ pub struct MultiplierData {
 // can we extract as much &logic as possible out and do all the &mut logic separately?
 // like have `tick` not actually make any changes to the game state but instead apply that based on the EventData
 impl Game {
-    pub fn new(team_a: Uuid, team_b: Uuid, day: usize, weather_override: Option<Weather>, world: &World, rng: &mut Rng) -> Game {
-        Game {
+    //day picks the weather table and tells the regular season from the postseason (day 99 on).
+    //who pitches is up to rotation.rs, not the day
+    pub fn new(team_a: Uuid, team_b: Uuid, day: usize, weather_override: Option<Weather>, world: &World, rng: &mut impl Roll) -> Game {
+        let mut game = Game {
             id: Uuid::new_v4(),
-            weather: if weather_override.is_some() { weather_override.unwrap() } else { Weather::generate(rng, world.season_ruleset, day) },
+            weather: if weather_override.is_some() { weather_override.unwrap() } else { Weather::generate(rng, &world.ruleset, day) },
             day,
             inning: 1,
             balls: 0,
@@ -161,21 +204,19 @@ impl Game {
             outs: 0,
             polarity: false,
             scoring_plays_inning: 0,
-            salmon_resets_inning: 0,
             events: Events::new(),
             started: false,
             scoreboard: Scoreboard {
                 home_team: GameTeam {
                     id: team_a,
-                    //todo: days
-                    pitcher: world.team(team_a).rotation[day % world.team(team_a).rotation.len()],
+                    pitcher: Uuid::nil(),
                     batter: None,
                     batter_index: 0,
-                    score: if world.team(team_a).mods.has(Mod::HomeFieldAdvantage) { 1.0 } else { 0.0 },
+                    score: 0.0,
                 },
                 away_team: GameTeam {
                     id: team_b,
-                    pitcher: world.team(team_b).rotation[day % world.team(team_b).rotation.len()],
+                    pitcher: Uuid::nil(),
                     batter: None,
                     batter_index: 0,
                     score: 0.0,
@@ -183,49 +224,78 @@ impl Game {
                 top: true,
             },
             runners: Baserunners::new(if world.team(team_b).mods.has(Mod::FifthBase) { 5 } else { 4 }),
-            linescore_home: vec![if world.team(team_a).mods.has(Mod::HomeFieldAdvantage) { 1.0 } else { 0.0 }],
-            linescore_away: vec![0.0],
-        }
+            runs: Vec::new(),
+            linescore: Linescore::new(),
+            shame: false,
+        };
+        game.prepare(world);
+        game
     }
 
-    fn base_sweep(&mut self) {
-        let mut new_runners = Baserunners::new(self.runners.base_number);
-        let mut scoring_play = false;
-        for runner in self.runners.iter() {
-            // todo: baserunner code is bad
-            if runner.base < self.runners.base_number - 1 {
-                new_runners.add(runner.base, runner.id);
-            } else {
-                scoring_play = true;
-            }
-        }
-        if scoring_play {
-            self.scoring_plays_inning += 1;
-        }
-        self.runners = new_runners;
+    //who's starting and what each team starts with. season games are all made before opening day,
+    //so this runs again on the day they're played to pick up shelled pitchers and shame from the day before
+    pub fn prepare(&mut self, world: &World) {
+        let home = world.team(self.scoreboard.home_team.id);
+        let away = world.team(self.scoreboard.away_team.id);
+        self.scoreboard.home_team.pitcher = rotation::starter(world, home.id);
+        self.scoreboard.away_team.pitcher = rotation::starter(world, away.id);
+        self.scoreboard.home_team.score = (if home.mods.has(Mod::HomeFieldAdvantage) { 1.0 } else { 0.0 }) - home.shame_runs;
+        self.scoreboard.away_team.score = -away.shame_runs;
+        self.linescore.set_start(false, self.scoreboard.home_team.score);
+        self.linescore.set_start(true, self.scoreboard.away_team.score);
     }
 
-    //note that this is only for runs scored on a regular event
-    fn score(&mut self, world: &mut World) {
+    //the home team's pregame odds, the away team's are 1 - odds. it costs about as much as playing
+    //the game, so it's worked out whenever someone asks and not before. ask once the pitchers are set
+    pub fn odds(&self, world: &World) -> f64 {
+        odds::home_odds(self, world)
+    }
+
+    //note that this is only for runs scored on a regular event.
+    //batter is whoever gets the rbi, None for steals, mild pitches and double plays
+    fn score(&mut self, world: &mut World, advancement: &Advancement, batter: Option<Uuid>) {
+        if advancement.any() {
+            self.scoring_plays_inning += 1;
+        }
         if self.outs < 3 {
             let mut runs_scored = 0.0;
-            for runner in self.runners.iter() {
-                if runner.base >= self.runners.base_number - 1 {
-                    runs_scored += self.get_run_value();
-                    runs_scored += world.player(runner.id).get_run_value();
-                    if world.player(runner.id).mods.has(Mod::FreeRefill) {
-                        self.outs -= 1;
-                        self.outs = self.outs.max(0); //can players refill the in with 0 outs
-                                                      //or was that a bug?
-                        world.player_mut(runner.id).mods.remove(Mod::FreeRefill);
-                    }
+            for scored in advancement.iter() {
+                let runner = scored.id;
+                runs_scored += self.get_run_value();
+                runs_scored += world.player(runner).get_run_value();
+                self.record_run(world, runner, batter, scored.pitcher);
+                if world.player(runner).mods.has(Mod::FreeRefill) {
+                    self.outs -= 1;
+                    self.outs = self.outs.max(0); //can players refill the in with 0 outs
+                                                  //or was that a bug?
+                    world.player_mut(runner).mods.remove(Mod::FreeRefill);
                 }
             }
             //run multipliers and sun wackiness here
-            self.scoreboard.batting_team_mut().score += runs_scored;
+            self.add_runs(runs_scored);
         }
     }
     
+    //runs for the batting team, in the current half inning's line too
+    fn add_runs(&mut self, runs: f64) {
+        self.scoreboard.batting_team_mut().score += runs;
+        self.linescore.add_runs(self.inning, self.scoreboard.top, runs);
+    }
+
+    //the run goes with the event being applied, which apply adds before anything else,
+    //so every run from one play points at the same entry in events
+    fn record_run(&mut self, world: &World, runner: Uuid, batter: Option<Uuid>, pitcher: Uuid) {
+        self.runs.push(RunRecord {
+            runner,
+            batter,
+            pitcher,
+            value: self.get_run_value() + world.player(runner).get_run_value(),
+            event: self.events.len() - 1,
+            inning: self.inning,
+            top: self.scoreboard.top,
+        });
+    }
+
     fn end_pa(&mut self) {
         let bt = self.scoreboard.batting_team_mut();
         bt.batter = None;
@@ -247,34 +317,34 @@ impl Game {
         let home_team = world.team(self.scoreboard.home_team.id);
         let away_team = world.team(self.scoreboard.away_team.id);
 
-        let mut eligible_players = Vec::new();
-        for i in 0..home_team.lineup.len() {
-            eligible_players.push(home_team.lineup[i]);
-        }
-        for i in 0..away_team.lineup.len() {
-            eligible_players.push(away_team.lineup[i]);
-        }
-        if only_current {
-            eligible_players.push(self.scoreboard.home_team.pitcher);
-            eligible_players.push(self.scoreboard.away_team.pitcher);
+        let pitchers: [&[Uuid]; 2] = if only_current {
+            [std::slice::from_ref(&self.scoreboard.home_team.pitcher), std::slice::from_ref(&self.scoreboard.away_team.pitcher)]
         } else {
-            for i in 0..home_team.rotation.len() {
-                eligible_players.push(home_team.rotation[i]);
-            }
-            for i in 0..away_team.rotation.len() {
-                eligible_players.push(away_team.rotation[i]);
-            }
-        }
-
-        eligible_players.retain(weight);
-        let idx = (roll * eligible_players.len() as f64).floor() as usize;
-        eligible_players[idx]
+            [&home_team.rotation, &away_team.rotation]
+        };
+        //counted first and then walked again, so there's no list of candidates to build
+        let eligible_players = || home_team.lineup.iter()
+            .chain(away_team.lineup.iter())
+            .chain(pitchers[0].iter())
+            .chain(pitchers[1].iter())
+            .filter(|uuid| weight(uuid));
+        let idx = (roll * eligible_players().count() as f64).floor() as usize;
+        *eligible_players().nth(idx).unwrap()
     }
 
     pub fn get_run_value(&self) -> f64 {
+        self.get_run_value_in(self.inning)
+    }
+
+    //what a run is worth in another inning, as far as we can tell from here
+    pub fn get_run_value_in(&self, inning: i16) -> f64 {
         let polarity_coeff = if self.polarity { -1.0 } else { 1.0 };
-        let sun_point_one_coeff = if let Weather::SunPointOne = self.weather { (self.inning as f64) / 10.0 } else { 1.0 };
-        let sum_sun_coeff = if let Weather::SumSun = self.weather { self.scoring_plays_inning as f64 } else { 0.0 };
+        let sun_point_one_coeff = if let Weather::SunPointOne = self.weather { (inning as f64) / 10.0 } else { 1.0 };
+        //sum sun counts up from zero every inning, so there's no telling for later ones
+        let sum_sun_coeff = match self.weather {
+            Weather::SumSun if inning == self.inning => self.scoring_plays_inning as f64,
+            _ => 0.0
+        };
         1.0 * polarity_coeff * sun_point_one_coeff + sum_sun_coeff
     }
 
@@ -323,18 +393,28 @@ impl Game {
         self.scoreboard.pitching_team_mut().pitcher = new;
     }
 
-    pub fn compute_multiplier_data(&self, world: &World) -> MultiplierData {
+    pub fn compute_multiplier_data<'a>(&self, world: &'a World) -> MultiplierData<'a> {
+        self.half_multiplier_data(world, self.scoreboard.top)
+    }
+
+    //the multiplier data for either half of the inning, for looking ahead
+    pub fn half_multiplier_data<'a>(&self, world: &'a World, top: bool) -> MultiplierData<'a> {
+        let (batting_team, pitching_team) = if top {
+            (&self.scoreboard.away_team, &self.scoreboard.home_team)
+        } else {
+            (&self.scoreboard.home_team, &self.scoreboard.away_team)
+        };
         MultiplierData {
-            //someone who knows about lifetimes more than me can probably
-            //make this code more efficient
-            batting_team_mods: world.team(self.scoreboard.batting_team().id).mods.clone(),
-            pitching_team_mods: world.team(self.scoreboard.pitching_team().id).mods.clone(), 
+            batting_team_mods: &world.team(batting_team.id).mods,
+            pitching_team_mods: &world.team(pitching_team.id).mods,
             weather: self.weather.clone(),
             day: self.day,
             runners_empty: self.runners.empty(),
-            top: self.scoreboard.top,
+            top,
             maximum_blaseball: self.runners.iter().count() == 3, //todo: kid named fifth base
             at_bats: 0, //todo
+            era: world.ruleset.era,
+            stadium: world.team(self.scoreboard.home_team.id).stadium.and_then(|id| world.stadiums.get(&id)),
         }
     }
 }
@@ -0,0 +1,89 @@
+//runs, hits and errors for one team in one inning
+#[derive(Clone, Debug, Default)]
+pub struct LinescoreInning {
+    pub runs: f64,
+    pub hits: u16,
+    pub errors: u16, //there's no fielding errors in the sim yet, so these stay at 0
+}
+
+//one entry per inning each team has come up in, extra innings included, indexed by inning
+//with top meaning the away team, like Scoreboard.top. inning 0 is what a team starts with before
+//anyone bats (home field advantage, shame), which no salmon can rewind.
+//sun 2 and black holes take their runs straight off the score, so the lines won't always add up to it
+#[derive(Clone, Debug)]
+pub struct Linescore {
+    pub away: Vec<LinescoreInning>,
+    pub home: Vec<LinescoreInning>,
+}
+
+impl Linescore {
+    pub fn new() -> Linescore {
+        Linescore {
+            away: vec![LinescoreInning::default(); 2],
+            home: vec![LinescoreInning::default()],
+        }
+    }
+
+    pub fn line(&self, top: bool) -> &Vec<LinescoreInning> {
+        if top { &self.away } else { &self.home }
+    }
+
+    fn inning_mut(&mut self, inning: i16, top: bool) -> &mut LinescoreInning {
+        let line = if top { &mut self.away } else { &mut self.home };
+        let idx = inning as usize;
+        if line.len() <= idx {
+            line.resize_with(idx + 1, LinescoreInning::default);
+        }
+        &mut line[idx]
+    }
+
+    pub fn inning(&self, inning: i16, top: bool) -> Option<&LinescoreInning> {
+        self.line(top).get(inning as usize)
+    }
+
+    //a half inning starting, so it shows up even if nobody scores in it
+    pub fn start(&mut self, inning: i16, top: bool) {
+        self.inning_mut(inning, top);
+    }
+
+    //the score a team starts the game on, see Game::prepare
+    pub fn set_start(&mut self, top: bool, runs: f64) {
+        self.inning_mut(0, top).runs = runs;
+    }
+
+    pub fn add_runs(&mut self, inning: i16, top: bool, runs: f64) {
+        self.inning_mut(inning, top).runs += runs;
+    }
+
+    pub fn add_hit(&mut self, inning: i16, top: bool) {
+        self.inning_mut(inning, top).hits += 1;
+    }
+
+    pub fn runs_in(&self, inning: i16, top: bool) -> f64 {
+        self.inning(inning, top).map_or(0.0, |i| i.runs)
+    }
+
+    //the salmon taking an inning's runs back. returns them so they can come off the score too.
+    //the hits still happened, so they stay
+    pub fn rewind(&mut self, inning: i16, top: bool) -> f64 {
+        std::mem::take(&mut self.inning_mut(inning, top).runs)
+    }
+
+    pub fn runs(&self, top: bool) -> f64 {
+        self.line(top).iter().map(|i| i.runs).sum()
+    }
+
+    pub fn hits(&self, top: bool) -> u16 {
+        self.line(top).iter().map(|i| i.hits).sum()
+    }
+
+    pub fn errors(&self, top: bool) -> u16 {
+        self.line(top).iter().map(|i| i.errors).sum()
+    }
+}
+
+impl Default for Linescore {
+    fn default() -> Linescore {
+        Linescore::new()
+    }
+}
@@ -0,0 +1,692 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::entities::{Player, World};
+use crate::formulas;
+use crate::mods::Mod;
+use crate::plate_appearance::{plate_appearance, PaOutcome, Situation};
+use crate::{Game, MultiplierData};
+
+//run expectancy and win probability from base-out states, with plate appearances from plate_appearance.rs
+//and runners moving the way Baserunners moves them.
+//runners are anonymous here, so their advancement chances are averaged over the batting team's lineup,
+//and runs are worth whatever Game::get_run_value_in says (player run values from wired and tired aren't counted).
+//lineups and pitchers are taken from when the model is built, so it wants rebuilding after an incineration
+
+//everything from this many runs on shares a bucket
+pub const MAX_RUNS: usize = 30;
+//probability mass smaller than this gets dropped
+const EPSILON: f64 = 1e-12;
+//the fixed points below stop after this many passes even if they haven't settled,
+//which only happens when a lineup can hardly make an out or extras can hardly end
+const MAX_PASSES: usize = 10_000;
+
+//where a plate appearance leaves the half inning
+#[derive(Clone, Copy, Debug)]
+struct Transition {
+    probability: f64,
+    outs: i16,
+    mask: usize,
+    runs: usize,
+}
+
+//one team's offense against the other's defense
+struct Offense {
+    lineup: Vec<Uuid>,
+    //[slot][outs][runners on][first occupied], from an 0-0 count
+    pa: Vec<[[[[f64; 11]; 2]; 2]; 3]>,
+    //[outs][mask][outcome]
+    transitions: Vec<Vec<Vec<Vec<Transition>>>>,
+    //the rest of the half inning from every state, [state_index][runs * lineup + next lead-off slot]
+    halves: Vec<Vec<f64>>,
+    //half innings starting with nobody on and nobody out, [lead-off slot]
+    clean: Vec<Vec<f64>>,
+    //every half inning from one inning up to the last inning (top) or the one before (bottom), where nothing can end the game,
+    //as (score in hundredths, next lead-off slot, probability). [inning][lead-off slot]
+    rest: Vec<Vec<Vec<(i64, usize, f64)>>>,
+}
+
+//scores are kept in hundredths so they can be compared exactly, like InningStatePlugin does with its 0.01
+fn key(score: f64) -> i64 {
+    (score * 100.0).round() as i64
+}
+
+fn collect(distribution: HashMap<(i64, usize), f64>) -> Vec<(i64, usize, f64)> {
+    distribution.into_iter().filter(|&(_, p)| p >= EPSILON).map(|((score, slot), p)| (score, slot, p)).collect()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct State {
+    pub inning: i16,
+    pub top: bool,
+    pub outs: i16,
+    //bit n is base n, home isn't in it
+    pub mask: usize,
+    pub away_slot: usize,
+    pub home_slot: usize,
+    //away minus home
+    pub lead: f64,
+}
+
+impl State {
+    pub fn from_game(game: &Game, world: &World) -> State {
+        let mut mask = 0;
+        for runner in game.runners.iter() {
+            if runner.base < game.runners.base_number - 1 {
+                mask |= 1 << runner.base;
+            }
+        }
+        let away = &game.scoreboard.away_team;
+        let home = &game.scoreboard.home_team;
+        State {
+            inning: game.inning,
+            top: game.scoreboard.top,
+            outs: game.outs,
+            mask,
+            away_slot: away.batter_index % world.team(away.id).lineup.len(),
+            home_slot: home.batter_index % world.team(home.id).lineup.len(),
+            lead: away.score - home.score,
+        }
+    }
+}
+
+//the base positions in a mask, lead runner first like the order runners usually sit in Baserunners
+fn positions(mask: usize, base_number: u8) -> Vec<u8> {
+    (0..base_number - 1).rev().filter(|base| mask & (1 << base) != 0).collect()
+}
+
+//Baserunners::advance_marked, for whichever runners `wants` picks. nobody blocks home
+fn advance_if(positions: &mut [u8], wants: usize, base_number: u8) {
+    let home = base_number - 1;
+    for i in 0..positions.len() {
+        let base = positions[i];
+        if wants & (1 << i) != 0 && base < home && (base + 1 >= home || !positions.contains(&(base + 1))) {
+            positions[i] += 1;
+        }
+    }
+}
+
+//Baserunners::settle, whoever reached home scores
+fn settle(positions: &[u8], base_number: u8) -> (usize, usize) {
+    let mut mask = 0;
+    let mut runs = 0;
+    for &base in positions {
+        if base >= base_number - 1 {
+            runs += 1;
+        } else {
+            mask |= 1 << base;
+        }
+    }
+    (mask, runs)
+}
+
+//every combination of runners trying to take an extra base
+fn advancements(runners: &[u8], chance: impl Fn(u8) -> f64, base_number: u8, forced: u8, batter: Option<u8>) -> Vec<(f64, usize, usize)> {
+    let mut results = Vec::new();
+    for wants in 0..(1usize << runners.len()) {
+        let mut probability = 1.0;
+        for (i, &base) in runners.iter().enumerate() {
+            let p = chance(base);
+            probability *= if wants & (1 << i) != 0 { p } else { 1.0 - p };
+        }
+        if probability == 0.0 {
+            continue;
+        }
+        let mut after: Vec<u8> = runners.iter().map(|base| base + forced).collect();
+        advance_if(&mut after, wants, base_number);
+        let (mut mask, mut runs) = settle(&after, base_number);
+        match batter {
+            //a quadruple without a fifth base on the field brings the batter straight home
+            Some(base) if base >= base_number - 1 => runs += 1,
+            Some(base) => mask |= 1 << base,
+            None => {}
+        }
+        results.push((probability, mask, runs));
+    }
+    results
+}
+
+struct Advancement {
+    hit: f64,
+    groundout: f64,
+    flyout: Vec<f64>,
+}
+
+fn transitions(outs: i16, mask: usize, outcome: PaOutcome, base_number: u8, advancement: &Advancement) -> Vec<Transition> {
+    let runners = positions(mask, base_number);
+    let ended = |outs: i16| vec![Transition { probability: 1.0, outs, mask: 0, runs: 0 }];
+    let after = |results: Vec<(f64, usize, usize)>, outs: i16| -> Vec<Transition> {
+        results.into_iter().map(|(probability, mask, runs)| Transition { probability, outs, mask, runs }).collect()
+    };
+    match outcome {
+        PaOutcome::Walk => {
+            let mut after = runners.clone();
+            let forced = (0..base_number).take_while(|base| mask & (1 << base) != 0).count() as u8;
+            for base in after.iter_mut() {
+                if *base < forced {
+                    *base += 1;
+                }
+            }
+            let (mask, runs) = settle(&after, base_number);
+            vec![Transition { probability: 1.0, outs, mask: mask | 1, runs }]
+        }
+        PaOutcome::Strikeout => {
+            if outs + 1 >= 3 {
+                ended(outs + 1)
+            } else {
+                vec![Transition { probability: 1.0, outs: outs + 1, mask, runs: 0 }]
+            }
+        }
+        PaOutcome::HomeRun => vec![Transition { probability: 1.0, outs, mask: 0, runs: runners.len() + 1 }],
+        PaOutcome::Quadruple | PaOutcome::Triple | PaOutcome::Double | PaOutcome::Single => {
+            let bases = match outcome {
+                PaOutcome::Quadruple => 4,
+                PaOutcome::Triple => 3,
+                PaOutcome::Double => 2,
+                _ => 1,
+            };
+            after(advancements(&runners, |_| advancement.hit, base_number, bases, Some(bases - 1)), outs)
+        }
+        PaOutcome::Flyout | PaOutcome::GroundOut if outs == 2 => ended(3),
+        PaOutcome::Flyout => after(advancements(&runners, |base| advancement.flyout[base as usize], base_number, 0, None), outs + 1),
+        PaOutcome::GroundOut => after(advancements(&runners, |_| advancement.groundout, base_number, 0, None), outs + 1),
+        PaOutcome::DoublePlay => {
+            if outs + 2 >= 3 {
+                return ended(outs + 2);
+            }
+            //Baserunners::pick_runner picks any of them
+            runners.iter().map(|&out| {
+                let after: Vec<u8> = runners.iter().filter(|&&base| base != out).map(|base| base + 1).collect();
+                let (mask, runs) = settle(&after, base_number);
+                Transition { probability: 1.0 / runners.len() as f64, outs: outs + 2, mask, runs }
+            }).collect()
+        }
+        PaOutcome::FieldersChoice => {
+            //Baserunners::pick_runner_fc
+            let out = (1..base_number - 1).take_while(|base| mask & (1 << base) != 0).last().unwrap_or(0);
+            let after: Vec<u8> = runners.iter().filter(|&&base| base != out).map(|base| base + 1).collect();
+            let (mask, runs) = settle(&after, base_number);
+            vec![Transition { probability: 1.0, outs: outs + 1, mask: mask | 1, runs }]
+        }
+    }
+}
+
+fn situation(world: &World, team: Uuid, batter: &Player, outs: i16, mask: usize, bases: u8) -> Situation {
+    let team_mods = &world.team(team).mods;
+    Situation {
+        outs,
+        runners_empty: mask == 0,
+        first_occupied: mask & 1 != 0,
+        bases,
+        max_balls: if batter.mods.has(Mod::WalkInThePark) || team_mods.has(Mod::WalkInThePark) { 3 } else { 4 },
+        max_strikes: if batter.mods.has(Mod::FourthStrike) || team_mods.has(Mod::FourthStrike) { 4 } else { 3 },
+        o_no: team_mods.has(Mod::ONo),
+    }
+}
+
+fn average(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, n) = values.fold((0.0, 0), |(sum, n), value| (sum + value, n + 1));
+    sum / n as f64
+}
+
+pub struct Model {
+    masks: usize,
+    //away bats in the top, home in the bottom
+    offenses: [Offense; 2],
+    //what polarity the rest tables were worked out under
+    polarity: bool,
+    //the home team's chances from a tie at the start of an extra inning, [negative polarity][away slot][home slot]
+    extras: [Vec<Vec<f64>>; 2],
+    //the first inning the game can end after, see game_end.rs
+    last_inning: i16,
+}
+
+impl Model {
+    pub fn new(game: &Game, world: &World) -> Model {
+        let base_number = game.runners.base_number;
+        let masks = 1 << (base_number - 1);
+        let offense = |top: bool| {
+            let (batting, pitching) = if top {
+                (&game.scoreboard.away_team, &game.scoreboard.home_team)
+            } else {
+                (&game.scoreboard.home_team, &game.scoreboard.away_team)
+            };
+            let multiplier_data = game.half_multiplier_data(world, top);
+            let data: &MultiplierData = &multiplier_data;
+            let ruleset = &world.ruleset;
+            let pitcher = world.player(pitching.pitcher);
+            let lineup = world.team(batting.id).lineup.clone();
+            let fielders: Vec<&Player> = world.team(pitching.id).lineup.iter().map(|&id| world.player(id)).collect();
+            let runners: Vec<&Player> = lineup.iter().map(|&id| world.player(id)).collect();
+            let bases = if world.team(batting.id).mods.has(Mod::FifthBase) { 5 } else { 4 };
+
+            let mut pa = Vec::new();
+            for &id in lineup.iter() {
+                let batter = world.player(id);
+                let mut table = [[[[0.0; 11]; 2]; 2]; 3];
+                for (outs, by_runners) in table.iter_mut().enumerate() {
+                    //runners on without anyone on first, and with
+                    for (mask, first) in [(0, 0), (2, 0), (1, 1)] {
+                        let situation = situation(world, batting.id, batter, outs as i16, mask, bases);
+                        by_runners[(mask != 0) as usize][first] = plate_appearance(pitcher, batter, &fielders, &situation, ruleset, data).outcomes;
+                    }
+                    by_runners[0][1] = by_runners[1][1];
+                }
+                pa.push(table);
+            }
+
+            let advancement = Advancement {
+                hit: average(runners.iter().flat_map(|runner| fielders.iter().map(move |fielder| formulas::hit_advancement_threshold(runner, fielder, ruleset, data).clamp(0.0, 1.0)))),
+                groundout: average(runners.iter().flat_map(|runner| fielders.iter().map(move |fielder| formulas::groundout_advancement_threshold(runner, fielder, ruleset, data).clamp(0.0, 1.0)))),
+                flyout: (0..base_number).map(|base| average(runners.iter().map(|runner| formulas::flyout_advancement_threshold(runner, base, ruleset, data).clamp(0.0, 1.0)))).collect(),
+            };
+            let transitions = (0..3).map(|outs| {
+                (0..masks).map(|mask| {
+                    PaOutcome::ALL.iter().map(|&outcome| transitions(outs, mask, outcome, base_number, &advancement)).collect()
+                }).collect()
+            }).collect();
+
+            Offense { lineup, pa, transitions, halves: Vec::new(), clean: Vec::new(), rest: Vec::new() }
+        };
+        let mut model = Model {
+            masks,
+            offenses: [offense(true), offense(false)],
+            polarity: game.polarity,
+            extras: [Vec::new(), Vec::new()],
+            last_inning: world.ruleset.innings,
+        };
+        for side in 0..2 {
+            let len = model.offenses[side].lineup.len();
+            model.offenses[side].halves = model.halves(side);
+            let clean: Vec<Vec<f64>> = (0..len).map(|slot| model.half_inning(side, 0, 0, slot, None)).collect();
+            //built from the back, so every inning just adds its own runs onto the next one's table
+            let last = if side == 0 { model.last_inning } else { model.last_inning - 1 };
+            let mut rest = vec![Vec::new(); last as usize + 2];
+            rest[last as usize + 1] = (0..len).map(|slot| vec![(0, slot, 1.0)]).collect();
+            for inning in (1..=last).rev() {
+                let value = key(game.get_run_value_in(inning));
+                rest[inning as usize] = (0..len).map(|slot| {
+                    let mut distribution = HashMap::new();
+                    for (i, q) in clean[slot].iter().enumerate() {
+                        for &(score, next, p) in rest[inning as usize + 1][i % len].iter() {
+                            *distribution.entry((score + (i / len) as i64 * value, next)).or_insert(0.0) += p * q;
+                        }
+                    }
+                    collect(distribution)
+                }).collect();
+            }
+            model.offenses[side].clean = clean;
+            model.offenses[side].rest = rest;
+        }
+        model.extras = [model.extras(false), model.extras(true)];
+        model
+    }
+
+    //extra innings go on for as long as they're tied, so this is a fixed point rather than a table built from the back.
+    //only whether runs count for or against matters here, not how much they're worth
+    fn extras(&self, negative: bool) -> Vec<Vec<f64>> {
+        let (away, home) = (&self.offenses[0], &self.offenses[1]);
+        let (away_len, home_len) = (away.lineup.len(), home.lineup.len());
+        let sign = if negative { -1 } else { 1 };
+        //(home wins outright, chance of a tie going into each pair of next slots)
+        let mut outright = vec![vec![0.0; home_len]; away_len];
+        let mut ties = vec![vec![vec![(0, 0, 0.0); 0]; home_len]; away_len];
+        for a in 0..away_len {
+            for h in 0..home_len {
+                let mut tied = HashMap::new();
+                for (i, p) in away.clean[a].iter().enumerate() {
+                    if *p < EPSILON {
+                        continue;
+                    }
+                    let away_runs = (i / away_len) as i64;
+                    //the home team leads after the top
+                    if sign * away_runs < 0 {
+                        outright[a][h] += p;
+                        continue;
+                    }
+                    for (j, q) in home.clean[h].iter().enumerate() {
+                        let lead = sign * (away_runs - (j / home_len) as i64);
+                        if lead < 0 {
+                            outright[a][h] += p * q;
+                        } else if lead == 0 {
+                            *tied.entry((i % away_len, j % home_len)).or_insert(0.0) += p * q;
+                        }
+                    }
+                }
+                ties[a][h] = tied.into_iter().map(|((a, h), p)| (a, h, p)).collect();
+            }
+        }
+        let mut extras = vec![vec![0.5; home_len]; away_len];
+        for _ in 0..MAX_PASSES {
+            let mut change: f64 = 0.0;
+            let next: Vec<Vec<f64>> = (0..away_len).map(|a| (0..home_len).map(|h| {
+                let p = outright[a][h] + ties[a][h].iter().map(|&(next_a, next_h, p)| p * extras[next_a][next_h]).sum::<f64>();
+                change = change.max((p - extras[a][h]).abs());
+                p
+            }).collect()).collect();
+            extras = next;
+            if change < EPSILON {
+                break;
+            }
+        }
+        extras
+    }
+
+    fn state_index(&self, side: usize, outs: i16, mask: usize, slot: usize) -> usize {
+        (outs as usize * self.masks + mask) * self.offenses[side].lineup.len() + slot
+    }
+
+    //one plate appearance, then whatever the half inning does from wherever it leaves things
+    fn step(&self, side: usize, outs: i16, mask: usize, slot: usize, pa: &[f64; 11], halves: &[Vec<f64>]) -> Vec<f64> {
+        let offense = &self.offenses[side];
+        let len = offense.lineup.len();
+        let next_slot = (slot + 1) % len;
+        let mut half = vec![0.0; MAX_RUNS * len];
+        for (i, outcome) in pa.iter().enumerate() {
+            if *outcome == 0.0 {
+                continue;
+            }
+            for t in offense.transitions[outs as usize][mask][i].iter() {
+                let q = outcome * t.probability;
+                if t.outs >= 3 {
+                    half[t.runs.min(MAX_RUNS - 1) * len + next_slot] += q;
+                    continue;
+                }
+                let after = &halves[self.state_index(side, t.outs, t.mask, next_slot)];
+                for runs in 0..MAX_RUNS {
+                    let total = (runs + t.runs).min(MAX_RUNS - 1);
+                    for end in 0..len {
+                        half[total * len + end] += q * after[runs * len + end];
+                    }
+                }
+            }
+        }
+        half
+    }
+
+    //the rest of the half inning from every base-out state and slot, by going over them until nothing changes.
+    //innings can go round the lineup, so there's no order that finishes everything before it's needed
+    fn halves(&self, side: usize) -> Vec<Vec<f64>> {
+        let len = self.offenses[side].lineup.len();
+        let mut halves = vec![vec![0.0; MAX_RUNS * len]; 3 * self.masks * len];
+        for _ in 0..MAX_PASSES {
+            let mut missing: f64 = 0.0;
+            for outs in (0..3).rev() {
+                for mask in 0..self.masks {
+                    for slot in 0..len {
+                        let pa = self.offenses[side].pa[slot][outs as usize][(mask != 0) as usize][mask & 1];
+                        let half = self.step(side, outs, mask, slot, &pa, &halves);
+                        missing = missing.max(1.0 - half.iter().sum::<f64>());
+                        let index = self.state_index(side, outs, mask, slot);
+                        halves[index] = half;
+                    }
+                }
+            }
+            if missing < EPSILON {
+                break;
+            }
+        }
+        halves
+    }
+
+    //what's left of a half inning as [runs * lineup + next lead-off slot]
+    //`first` replaces the first plate appearance, for one that's already underway
+    fn half_inning(&self, side: usize, outs: i16, mask: usize, slot: usize, first: Option<[f64; 11]>) -> Vec<f64> {
+        if outs >= 3 {
+            let mut ended = vec![0.0; MAX_RUNS * self.offenses[side].lineup.len()];
+            ended[slot] = 1.0;
+            return ended;
+        }
+        let halves = &self.offenses[side].halves;
+        match first {
+            Some(pa) => self.step(side, outs, mask, slot, &pa, halves),
+            None => halves[self.state_index(side, outs, mask, slot)].clone(),
+        }
+    }
+
+    //the distribution of runs (not run values) the batting side scores in the rest of a half inning
+    pub fn run_distribution(&self, top: bool, outs: i16, mask: usize, slot: usize) -> Vec<f64> {
+        let side = if top { 0 } else { 1 };
+        let len = self.offenses[side].lineup.len();
+        let half = self.half_inning(side, outs, mask, slot, None);
+        (0..MAX_RUNS).map(|runs| half[runs * len..(runs + 1) * len].iter().sum()).collect()
+    }
+
+    //expected runs for the rest of a half inning, for every base-out state
+    pub fn run_expectancy_table(&self, top: bool, slot: usize) -> Vec<(i16, usize, f64)> {
+        let mut table = Vec::new();
+        for outs in 0..3 {
+            for mask in 0..self.masks {
+                let distribution = self.run_distribution(top, outs, mask, slot);
+                table.push((outs, mask, distribution.iter().enumerate().map(|(runs, p)| runs as f64 * p).sum()));
+            }
+        }
+        table
+    }
+
+    //the current plate appearance from its current count, if there is one
+    fn current_pa(&self, game: &Game, world: &World) -> Option<[f64; 11]> {
+        let batter = world.player(game.batter()?);
+        let pitcher = world.player(game.pitcher());
+        let fielders: Vec<&Player> = world.team(game.scoreboard.pitching_team().id).lineup.iter().map(|&id| world.player(id)).collect();
+        let situation = Situation::from_game(game, world);
+        let pa = plate_appearance(pitcher, batter, &fielders, &situation, &world.ruleset, &game.compute_multiplier_data(world));
+        Some(pa.from_count(game.balls, game.strikes))
+    }
+
+    //expected run value the batting side adds before the half inning is over.
+    //with walk-offs on, this still counts the runs after the winning one, which the game wouldn't get to
+    pub fn run_expectancy(&self, game: &Game, world: &World) -> f64 {
+        let state = State::from_game(game, world);
+        let side = if state.top { 0 } else { 1 };
+        let slot = if state.top { state.away_slot } else { state.home_slot };
+        let len = self.offenses[side].lineup.len();
+        let half = self.half_inning(side, state.outs, state.mask, slot, self.current_pa(game, world));
+        let runs: f64 = half.iter().enumerate().map(|(i, p)| (i / len) as f64 * p).sum();
+        runs * game.get_run_value_in(state.inning)
+    }
+
+    //the home team's chances of winning from here
+    pub fn win_probability(&self, game: &Game, world: &World) -> f64 {
+        self.win_probability_from(game, &State::from_game(game, world), self.current_pa(game, world))
+    }
+
+    pub fn win_probability_from(&self, game: &Game, state: &State, first: Option<[f64; 11]>) -> f64 {
+        if state.inning >= self.last_inning {
+            self.late_win_probability(game, state, first)
+        } else {
+            self.early_win_probability(game, state, first)
+        }
+    }
+
+    //where the game's at, as (score in hundredths, next lead-off slot) for the batting side by the end of `rest`
+    fn finish(&self, game: &Game, side: usize, state: &State, first: Option<[f64; 11]>) -> Vec<(i64, usize, f64)> {
+        let offense = &self.offenses[side];
+        let len = offense.lineup.len();
+        let slot = if side == 0 { state.away_slot } else { state.home_slot };
+        let half = self.half_inning(side, state.outs, state.mask, slot, first);
+        let value = key(game.get_run_value_in(state.inning));
+        let flip = if game.polarity != self.polarity { -1 } else { 1 };
+        let mut distribution = HashMap::new();
+        for (i, q) in half.iter().enumerate() {
+            if *q < EPSILON {
+                continue;
+            }
+            for &(score, next, p) in offense.rest[state.inning as usize + 1][i % len].iter() {
+                *distribution.entry(((i / len) as i64 * value + flip * score, next)).or_insert(0.0) += p * q;
+            }
+        }
+        collect(distribution)
+    }
+
+    //before the last inning, both teams' innings are independent of each other until its top is over.
+    //who leads off for the away team in extra innings is taken to be independent of the score
+    fn early_win_probability(&self, game: &Game, state: &State, first: Option<[f64; 11]>) -> f64 {
+        let flip = if game.polarity != self.polarity { -1 } else { 1 };
+        let upcoming = |side: usize, inning: i16, slot: usize| -> Vec<(i64, usize, f64)> {
+            self.offenses[side].rest[inning as usize][slot].iter().map(|&(score, next, p)| (flip * score, next, p)).collect()
+        };
+        let (away, home) = if state.top {
+            (self.finish(game, 0, state, first), upcoming(1, state.inning, state.home_slot))
+        } else {
+            (upcoming(0, state.inning + 1, state.away_slot), self.finish(game, 1, state, first))
+        };
+
+        let home_offense = &self.offenses[1];
+        let home_len = home_offense.lineup.len();
+        let mut away_slots = vec![0.0; self.offenses[0].lineup.len()];
+        let mut away_scores: HashMap<i64, f64> = HashMap::new();
+        for &(score, slot, p) in away.iter() {
+            away_slots[slot] += p;
+            *away_scores.entry(score).or_insert(0.0) += p;
+        }
+        let extras = &self.extras[game.polarity as usize];
+        let extra: Vec<f64> = (0..home_len).map(|h| away_slots.iter().enumerate().map(|(a, p)| p * extras[a][h]).sum()).collect();
+        //for the bottom of the last inning from each slot: the chance of scoring at least so many runs,
+        //and the chance of scoring exactly so many times how extra innings go from there
+        let mut at_least = vec![vec![0.0; MAX_RUNS + 1]; home_len];
+        let mut exactly = vec![vec![0.0; MAX_RUNS]; home_len];
+        for slot in 0..home_len {
+            for (i, q) in home_offense.clean[slot].iter().enumerate() {
+                exactly[slot][i / home_len] += q * extra[i % home_len];
+                at_least[slot][i / home_len] += q;
+            }
+            for runs in (0..MAX_RUNS).rev() {
+                at_least[slot][runs] += at_least[slot][runs + 1];
+            }
+        }
+        let value = key(game.get_run_value_in(self.last_inning));
+        //the home team's chances from a lead after the top of the last inning, with `slot` leading off the bottom
+        let from = |lead: i64, slot: usize| -> f64 {
+            if lead < 0 {
+                1.0
+            } else if value <= 0 {
+                //runs don't help the home team catch up, at best they stay tied
+                if lead == 0 { exactly[slot][0] } else { 0.0 }
+            } else {
+                let tying = lead / value;
+                let winning = (tying + 1) as usize;
+                let wins = if winning < MAX_RUNS { at_least[slot][winning] } else { 0.0 };
+                let ties = if lead % value == 0 && (tying as usize) < MAX_RUNS { exactly[slot][tying as usize] } else { 0.0 };
+                wins + ties
+            }
+        };
+
+        let lead = key(state.lead);
+        let mut home_wins = 0.0;
+        for (&away_score, &p) in away_scores.iter() {
+            for &(home_score, slot, q) in home.iter() {
+                home_wins += p * q * from(lead + away_score - home_score, slot);
+            }
+        }
+        home_wins
+    }
+
+    //from the last inning on, every half inning can end the game, so both teams get followed together
+    fn late_win_probability(&self, game: &Game, state: &State, first: Option<[f64; 11]>) -> f64 {
+        let mut home_wins = 0.0;
+        let mut inning = state.inning;
+        let mut top = state.top;
+        //(lead in hundredths, away slot, home slot)
+        let mut games: HashMap<(i64, usize, usize), f64> = HashMap::new();
+        games.insert((key(state.lead), state.away_slot, state.home_slot), 1.0);
+        let mut current = Some((state.outs, state.mask, first));
+        while !games.is_empty() {
+            //anything still going at the start of a new inning is tied
+            if top && current.is_none() {
+                let extras = &self.extras[game.polarity as usize];
+                return home_wins + games.iter().map(|(&(_, a, h), p)| p * extras[a][h]).sum::<f64>();
+            }
+            let side = if top { 0 } else { 1 };
+            let len = self.offenses[side].lineup.len();
+            let value = key(game.get_run_value_in(inning));
+            let mut next = HashMap::new();
+            for (&(lead, away_slot, home_slot), &p) in games.iter() {
+                let slot = if top { away_slot } else { home_slot };
+                let computed;
+                let half = match current {
+                    Some((outs, mask, first)) => {
+                        computed = self.half_inning(side, outs, mask, slot, first);
+                        &computed
+                    }
+                    None => &self.offenses[side].clean[slot],
+                };
+                for (i, q) in half.iter().enumerate() {
+                    if p * q < EPSILON {
+                        continue;
+                    }
+                    let runs = (i / len) as i64;
+                    let (lead, away_slot, home_slot) = if top {
+                        (lead + runs * value, i % len, home_slot)
+                    } else {
+                        (lead - runs * value, away_slot, i % len)
+                    };
+                    //the same checks as game_end.rs at the end of a half. walk-offs don't need their own:
+                    //runs are all worth the same within a half here, so once the home team leads in the bottom
+                    //they can only pull further ahead, and the half playing out wins the same games.
+                    //what the model doesn't see is anything that takes a lead back mid-half (a polarity switch,
+                    //triple threat, sun 2 and black holes), which a walk-off would have cut off
+                    if top && lead < 0 || !top && lead != 0 {
+                        if lead < 0 {
+                            home_wins += p * q;
+                        }
+                        continue;
+                    }
+                    *next.entry((lead, away_slot, home_slot)).or_insert(0.0) += p * q;
+                }
+            }
+            current = None;
+            games = next;
+            if top {
+                top = false;
+            } else {
+                top = true;
+                inning += 1;
+            }
+        }
+        home_wins
+    }
+
+    //the expected absolute change in the home team's win probability over the current plate appearance.
+    //divide by the average over a game to get a leverage index
+    pub fn leverage(&self, game: &Game, world: &World) -> f64 {
+        let state = State::from_game(game, world);
+        if state.outs >= 3 {
+            return 0.0;
+        }
+        let pa = match self.current_pa(game, world) {
+            Some(pa) => pa,
+            None => {
+                let slot = if state.top { state.away_slot } else { state.home_slot };
+                self.offenses[if state.top { 0 } else { 1 }].pa[slot][state.outs as usize][(state.mask != 0) as usize][state.mask & 1]
+            }
+        };
+        let now = self.win_probability_from(game, &state, Some(pa));
+        let side = if state.top { 0 } else { 1 };
+        let value = game.get_run_value_in(state.inning);
+        //plenty of outcomes end up in the same place
+        let mut outcomes: HashMap<(i16, usize, usize), f64> = HashMap::new();
+        for (i, p) in pa.iter().enumerate() {
+            for t in self.offenses[side].transitions[state.outs as usize][state.mask][i].iter() {
+                *outcomes.entry((t.outs.min(3), t.mask, t.runs)).or_insert(0.0) += p * t.probability;
+            }
+        }
+        let mut swing = 0.0;
+        for (&(outs, mask, runs), p) in outcomes.iter() {
+            let mut after = state;
+            after.outs = outs;
+            after.mask = mask;
+            let runs = runs as f64 * value;
+            if state.top {
+                after.away_slot = (state.away_slot + 1) % self.offenses[0].lineup.len();
+                after.lead += runs;
+            } else {
+                after.home_slot = (state.home_slot + 1) % self.offenses[1].lineup.len();
+                after.lead -= runs;
+            }
+            swing += p * (self.win_probability_from(game, &after, None) - now).abs();
+        }
+        swing
+    }
+}
@@ -1,7 +1,8 @@
-use strum::EnumString;
+use std::str::FromStr;
+use strum::{EnumCount, EnumIter, EnumString, IntoEnumIterator, IntoStaticStr};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
-// todo: repr u16 for compactness?
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr, EnumIter, EnumCount)]
 #[strum(serialize_all="SCREAMING_SNAKE_CASE")]
 pub enum Mod {
     TargetedShame,
@@ -68,6 +69,103 @@ pub enum Mod {
     Scattered
 }
 
+//mods that can't be held together: getting one takes away the others in its group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModGroup {
+    Heat,
+    Coffee,
+    Performance,
+}
+
+//who a mod can be on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModHolder {
+    Player,
+    Team,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ModInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    //what the sim gives it when it hands the mod out itself
+    pub lifetime: ModLifetime,
+    pub holder: ModHolder,
+    pub group: Option<ModGroup>,
+}
+
+impl Mod {
+    //Mods keeps one bit per mod
+    pub(crate) const fn bit(self) -> u64 {
+        1 << self as u8
+    }
+
+    pub fn info(self) -> ModInfo {
+        use ModHolder::{Both, Player, Team};
+        use ModLifetime::{Game, Permanent, Week};
+        let (name, description, lifetime, holder, group) = match self {
+            Mod::TargetedShame => ("Targeted Shame", "Starts the next game with runs taken away.", Permanent, Team, None),
+            Mod::Flinch => ("Flinch", "Won't swing until they have a strike.", Permanent, Player, None),
+            Mod::Mild => ("Mild", "Sometimes throws a Mild Pitch, advancing every runner.", Permanent, Player, None),
+            Mod::Reverberating => ("Reverberating", "Sometimes bats again right away.", Permanent, Player, None),
+            Mod::Fireproof => ("Fireproof", "Can't be incinerated.", Permanent, Both, None),
+            Mod::Soundproof => ("Soundproof", "Resists Feedback, tangling someone else.", Permanent, Player, None),
+            Mod::Shelled => ("Shelled", "Stuck in a peanut shell and can't bat or pitch.", Permanent, Player, None),
+            Mod::LifeOfTheParty => ("Life of the Party", "Parties more often.", Permanent, Team, None),
+            Mod::Gravity => ("Gravity", "Can't be moved by Reverb.", Permanent, Player, None),
+            Mod::NightVision => ("Night Vision", "Bats better in Solar Eclipses.", Permanent, Player, None),
+            Mod::FourthStrike => ("Fourth Strike", "Strikes out on the fourth strike instead of the third.", Permanent, Both, None),
+            Mod::DebtU => ("Debt", "Pitches at batters, who may catch something.", Permanent, Player, None),
+            Mod::Unstable => ("Unstable", "Can chain incinerations to the opposing team.", Week, Player, None),
+            Mod::Superallergic => ("Superallergic", "Can be incinerated by a peanut.", Permanent, Player, None),
+            Mod::Spicy => ("Spicy", "Heats up after two hits in a row.", Permanent, Player, None),
+            Mod::HeatingUp => ("Heating Up", "One more hit to be Red Hot.", Permanent, Player, Some(ModGroup::Heat)),
+            Mod::RedHot => ("Red Hot", "Much better at hitting, until they don't.", Permanent, Player, Some(ModGroup::Heat)),
+            Mod::Minimized => ("Minimized", "Protects teammates from incinerations.", Permanent, Player, None),
+            Mod::Electric => ("Electric", "Sometimes zaps a strike away.", Permanent, Both, None),
+            Mod::RefinancedDebt => ("Refinanced Debt", "Pitches at batters, who may catch something.", Permanent, Player, None),
+            Mod::Flickering => ("Flickering", "Can be swapped by Feedback more often.", Week, Player, None),
+            Mod::Stable => ("Stable", "Can't be chained to by Unstable players.", Permanent, Player, None),
+            Mod::HomeFieldAdvantage => ("Home Field Advantage", "Starts home games with a run.", Permanent, Team, None),
+            Mod::BaseInstincts => ("Base Instincts", "Walks can take extra bases.", Permanent, Player, None),
+            Mod::AffinityForCrows => ("Affinity for Crows", "Pitches much better in Birds.", Permanent, Player, None),
+            Mod::Growth => ("Growth", "Gets better as the season goes on.", Permanent, Team, None),
+            Mod::ConsolidatedDebt => ("Consolidated Debt", "Pitches at batters, who may catch something.", Permanent, Player, None),
+            Mod::Repeating => ("Repeating", "Bats again in Reverb.", Week, Player, None),
+            Mod::FifthBase => ("Fifth Base", "Has a fifth base to run.", Permanent, Team, None),
+            Mod::Charm => ("Charm", "Sometimes charms their way through a plate appearance.", Permanent, Player, None),
+            Mod::SuperFlickering => ("Super Flickering", "Can be swapped by Feedback even more often.", Permanent, Player, None),
+            Mod::Squiddish => ("Squiddish", "Replaced from the Hall when incinerated.", Permanent, Player, None),
+            Mod::Siphon => ("Siphon", "Blooddrains with effects on the game.", Permanent, Player, None),
+            Mod::FriendOfCrows => ("Friend of Crows", "Birds peck opposing batters out in Birds.", Permanent, Player, None),
+            Mod::FireEater => ("Fire Eater", "Eats incinerations and becomes Magmatic.", Permanent, Player, None),
+            Mod::Magmatic => ("Magmatic", "Their next hit is a home run.", Permanent, Player, None),
+            Mod::HoneyRoasted => ("Honey Roasted", "Sometimes gives peanuts a taste of the infinite.", Permanent, Player, None),
+            Mod::Traveling => ("Traveling", "Plays better away from home.", Permanent, Team, None),
+            Mod::Haunted => ("Haunted", "Can be inhabited by a player from the Hall.", Permanent, Player, None),
+            Mod::Sealant => ("Sealant", "Can't be blooddrained.", Permanent, Team, None),
+            Mod::Blaserunning => ("Blaserunning", "Scores a little extra on stolen bases.", Permanent, Player, None),
+            Mod::BirdSeed => ("Bird Seed", "Birds sometimes free Shelled players.", Permanent, Team, None),
+            Mod::Superyummy => ("Superyummy", "Plays much better in Peanuts, worse otherwise.", Permanent, Player, None),
+            Mod::Overperforming => ("Overperforming", "All their stats are boosted.", Game, Player, Some(ModGroup::Performance)),
+            Mod::Underperforming => ("Underperforming", "All their stats are lowered.", Game, Player, Some(ModGroup::Performance)),
+            Mod::WalkInThePark => ("Walk in the Park", "Walks after three balls instead of four.", Permanent, Both, None),
+            Mod::ONo => ("O No", "Can't strike out looking with no balls.", Permanent, Team, None),
+            Mod::Wired => ("Wired", "Runs they score count for more.", Game, Player, Some(ModGroup::Coffee)),
+            Mod::Tired => ("Tired", "Runs they score count for less.", Game, Player, Some(ModGroup::Coffee)),
+            Mod::FreeRefill => ("Free Refill", "Their next run refills an out.", Game, Player, None),
+            Mod::TripleThreat => ("Triple Threat", "Pitches a little better, until it wears off.", Permanent, Player, None),
+            Mod::Perk => ("Perk", "Plays better in Coffee weathers.", Permanent, Player, None),
+            Mod::Elsewhere => ("Elsewhere", "Somewhere else, and can't play.", Permanent, Player, None),
+            Mod::Scattered => ("Scattered", "Their letters are scattered, and so are their vibes.", Permanent, Player, None),
+        };
+        ModInfo { name, description, lifetime, holder, group }
+    }
+}
+
+const _: () = assert!(Mod::COUNT <= 64, "Mods only has 64 bits per lifetime");
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ModLifetime {
     Game,
@@ -77,55 +175,114 @@ pub enum ModLifetime {
     Permanent,
 }
 
+//attributes the sim doesn't know about yet,
+//kept so they survive a round trip through the sim
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct ModWithLifetime {
+struct UnknownMod {
     lifetime: ModLifetime,
-    the_mod: Mod, // mod is a keyword lmao
+    name: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Mods {
-    // todo: store this as a set? or a smallvec?
-    // we only have <10 entries so i think searching a vec might be faster anyway
-    mods: Vec<ModWithLifetime>,
+    //a bitset of Mod per lifetime, indexed like ModLifetime
+    mods: [u64; 5],
+    unknown: Vec<UnknownMod>,
 }
 
 impl Mods {
     pub fn new() -> Mods {
-        Mods { mods: Vec::new() }
+        Mods::default()
+    }
+
+    //parses an attribute name, keeping it as an unknown mod if it doesn't parse
+    //returns whether the attribute was recognised
+    pub fn add_attr(&mut self, name: &str, lifetime: ModLifetime) -> bool {
+        if let Ok(m) = Mod::from_str(name) {
+            self.add(m, lifetime);
+            true
+        } else {
+            let um = UnknownMod {
+                lifetime,
+                name: name.to_string(),
+            };
+            if !self.unknown.contains(&um) {
+                self.unknown.push(um);
+            }
+            false
+        }
+    }
+
+    pub fn unknown(&self) -> impl Iterator<Item = &str> {
+        self.unknown.iter().map(|x| x.name.as_str())
+    }
+
+    //attribute names with the given lifetime, known and unknown
+    pub fn attrs(&self, lifetime: ModLifetime) -> Vec<String> {
+        let bits = self.mods[lifetime as usize];
+        let known = Mod::iter()
+            .filter(|m| bits & m.bit() != 0)
+            .map(|m| {
+                let name: &'static str = m.into();
+                name.to_string()
+            });
+        let unknown = self.unknown
+            .iter()
+            .filter(|x| x.lifetime == lifetime)
+            .map(|x| x.name.clone());
+        known.chain(unknown).collect()
     }
 
     pub fn has(&self, m: Mod) -> bool {
-        self.mods.iter().any(|x| x.the_mod == m)
+        self.has_any(m.bit())
+    }
+
+    //for a mask of Mod::bit()s
+    pub(crate) fn has_any(&self, mask: u64) -> bool {
+        self.mods.iter().fold(0, |all, bits| all | bits) & mask != 0
+    }
+
+    //with the lifetime the sim gives it, see Mod::info
+    pub fn add_default(&mut self, m: Mod) {
+        self.add(m, m.info().lifetime);
     }
 
     pub fn add(&mut self, m: Mod, lifetime: ModLifetime) {
-        let ml = ModWithLifetime {
-            the_mod: m,
-            lifetime: lifetime,
-        };
-        if !self.mods.contains(&ml) {
-            self.mods.push(ml);
+        if let Some(group) = m.info().group {
+            let others = Mod::iter()
+                .filter(|&other| other != m && other.info().group == Some(group))
+                .fold(0, |mask, other| mask | other.bit());
+            for bits in self.mods.iter_mut() {
+                *bits &= !others;
+            }
         }
+        self.mods[lifetime as usize] |= m.bit();
     }
 
     pub fn remove(&mut self, m: Mod) {
-        self.mods.retain(|x| x.the_mod != m)
+        for bits in self.mods.iter_mut() {
+            *bits &= !m.bit();
+        }
+    }
+
+    fn clear(&mut self, lifetime: ModLifetime) {
+        self.mods[lifetime as usize] = 0;
+        self.unknown.retain(|x| x.lifetime != lifetime);
     }
 
     pub fn clear_game(&mut self) {
-        self.mods.retain(|x| x.lifetime != ModLifetime::Game);
+        self.clear(ModLifetime::Game);
     }
 
     pub fn clear_weekly(&mut self) {
-        self.mods.retain(|x| x.lifetime != ModLifetime::Week);
+        self.clear(ModLifetime::Week);
     }
 
     pub fn clear_season(&mut self) {
-        self.mods.retain(|x| x.lifetime != ModLifetime::Season);
+        self.clear(ModLifetime::Season);
     }
 
     pub fn clear_legendary_item(&mut self) {
-        self.mods.retain(|x| x.lifetime != ModLifetime::LegendaryItem);
+        self.mods[ModLifetime::LegendaryItem as usize] = 0;
     }
 }
@@ -0,0 +1,160 @@
+use crate::entities::{AttrCategory, PlayerAttr};
+use crate::mods::{Mod, Mods};
+use crate::ruleset::Era;
+use crate::{MultiplierData, Weather};
+
+//what stat multipliers are made of. every mod that changes stats gets an entry per ruleset
+//saying whose mod it has to be, which stat categories it can touch and how it combines with the others.
+//additive contributions are summed onto 1, multiplicative ones scale the sum afterwards.
+//all the stat mods up to season 14 are additive
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combine {
+    Additive,
+    Multiplicative,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Owner {
+    Player,
+    Team,
+}
+
+pub struct Contribution {
+    pub source: Mod,
+    pub owner: Owner,
+    pub combine: Combine,
+    pub categories: &'static [AttrCategory],
+    //what it adds (or multiplies by) for this stat, whenever the owner has the mod
+    pub amount: fn(PlayerAttr, &MultiplierData) -> f64,
+}
+
+const ALL: &[AttrCategory] = &[AttrCategory::Batting, AttrCategory::Pitching, AttrCategory::Baserunning, AttrCategory::Defense, AttrCategory::Vibes];
+const BATTING: &[AttrCategory] = &[AttrCategory::Batting];
+const PITCHING: &[AttrCategory] = &[AttrCategory::Pitching];
+
+const S11: [Contribution; 7] = [
+    Contribution {
+        source: Mod::Overperforming,
+        owner: Owner::Player,
+        combine: Combine::Additive,
+        categories: ALL,
+        amount: |_, _| 0.2,
+    },
+    Contribution {
+        source: Mod::Underperforming,
+        owner: Owner::Player,
+        combine: Combine::Additive,
+        categories: ALL,
+        amount: |_, _| -0.2,
+    },
+    Contribution {
+        source: Mod::Growth,
+        owner: Owner::Team,
+        combine: Combine::Additive,
+        categories: ALL,
+        amount: |_, data| 0.05f64.min(data.day as f64 / 99.0 * 0.05),
+    },
+    Contribution {
+        source: Mod::Traveling,
+        owner: Owner::Team,
+        combine: Combine::Additive,
+        categories: &[AttrCategory::Batting, AttrCategory::Pitching, AttrCategory::Defense],
+        amount: |attr, data| {
+            let mut amount = 0.0;
+            let away = data.top && attr.is_batting() || !data.top && attr.is_pitching();
+            //buoy, path, thwack, cold, ruth
+            if away && !([0, 5, 6, 8, 10].contains(&attr.discr())) {
+                amount += 0.05;
+            }
+            if !data.top && attr.is_defense() {
+                amount += 0.05;
+            }
+            amount
+        },
+    },
+    Contribution {
+        source: Mod::AffinityForCrows,
+        owner: Owner::Player,
+        combine: Combine::Additive,
+        categories: PITCHING,
+        amount: |_, data| if let Weather::Birds = data.weather { 0.5 } else { 0.0 },
+    },
+    Contribution {
+        source: Mod::RedHot,
+        owner: Owner::Player,
+        combine: Combine::Additive,
+        categories: BATTING,
+        amount: |attr, _| match attr {
+            PlayerAttr::Thwackability => 4.0,
+            PlayerAttr::Moxie => 2.0,
+            _ => 0.0,
+        },
+    },
+    Contribution {
+        source: Mod::NightVision,
+        owner: Owner::Player,
+        combine: Combine::Additive,
+        categories: BATTING,
+        amount: |_, data| if let Weather::Eclipse = data.weather { 0.5 } else { 0.0 },
+    },
+];
+
+//every mod the contributions come from, as Mod::bit()s
+const fn sources(contributions: &[Contribution]) -> u64 {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < contributions.len() {
+        mask |= contributions[i].source.bit();
+        i += 1;
+    }
+    mask
+}
+
+//an era's contributions, with the mods they come from worked out ahead of time
+pub struct Table {
+    pub contributions: &'static [Contribution],
+    pub sources: u64,
+}
+
+static S11_TABLE: Table = Table { contributions: &S11, sources: sources(&S11) };
+
+//none of the stat mods changed between the eras sandbox supports
+pub fn table(era: Era) -> &'static Table {
+    match era {
+        Era::S11 | Era::S12 | Era::S13 | Era::S14 => &S11_TABLE,
+    }
+}
+
+pub fn multiplier(attr: PlayerAttr, mods: &Mods, team_mods: &Mods, data: &MultiplierData) -> f64 {
+    let table = table(data.era);
+    //most players and teams have none of these, this gets called for every stat on every pitch
+    if !mods.has_any(table.sources) && !team_mods.has_any(table.sources) {
+        return 1.0;
+    }
+    combine(table.contributions, attr, mods, team_mods, data)
+}
+
+//1 plus the additive contributions that apply to this stat, times the multiplicative ones
+pub fn combine(contributions: &[Contribution], attr: PlayerAttr, mods: &Mods, team_mods: &Mods, data: &MultiplierData) -> f64 {
+    let mut sum = 1.0;
+    let mut product = 1.0;
+    for contribution in contributions {
+        if !contribution.categories.contains(&attr.category()) {
+            continue;
+        }
+        let owner_mods = match contribution.owner {
+            Owner::Player => mods,
+            Owner::Team => team_mods,
+        };
+        if !owner_mods.has(contribution.source) {
+            continue;
+        }
+        let amount = (contribution.amount)(attr, data);
+        match contribution.combine {
+            Combine::Additive => sum += amount,
+            Combine::Multiplicative => product *= amount,
+        }
+    }
+    sum * product
+}
@@ -0,0 +1,73 @@
+use crate::entities::{Player, World};
+use crate::mods::Mod;
+use crate::plate_appearance::{plate_appearance, PaOutcome, Situation};
+use crate::Game;
+
+//pregame odds, the way blaseball showed them before every game.
+//each team's runs come from the exact plate appearance distributions of its lineup against the other pitcher,
+//put through base runs, and the runs become odds through the pythagorean expectation
+
+//the usual exponent for baseball, nothing here has been fitted to blaseball
+const PYTHAGOREAN_EXPONENT: f64 = 1.83;
+const OUTS_PER_GAME: f64 = 27.0;
+
+//expected runs per game for whoever bats in this half
+fn expected_runs(game: &Game, world: &World, top: bool) -> f64 {
+    let (batting, pitching) = if top {
+        (&game.scoreboard.away_team, &game.scoreboard.home_team)
+    } else {
+        (&game.scoreboard.home_team, &game.scoreboard.away_team)
+    };
+    let multiplier_data = game.half_multiplier_data(world, top);
+    let pitcher = world.player(pitching.pitcher);
+    let fielders: Vec<&Player> = world.team(pitching.id).lineup.iter().map(|&id| world.player(id)).collect();
+    let team = world.team(batting.id);
+
+    //per plate appearance, averaged over the lineup
+    let mut rates = [0.0; 11];
+    for &id in team.lineup.iter() {
+        let batter = world.player(id);
+        //bases empty with nobody out, so no double plays or fielder's choices
+        let situation = Situation {
+            outs: 0,
+            runners_empty: true,
+            first_occupied: false,
+            bases: if team.mods.has(Mod::FifthBase) { 5 } else { 4 },
+            max_balls: if batter.mods.has(Mod::WalkInThePark) || team.mods.has(Mod::WalkInThePark) { 3 } else { 4 },
+            max_strikes: if batter.mods.has(Mod::FourthStrike) || team.mods.has(Mod::FourthStrike) { 4 } else { 3 },
+            o_no: team.mods.has(Mod::ONo),
+        };
+        let pa = plate_appearance(pitcher, batter, &fielders, &situation, &world.ruleset, &multiplier_data);
+        for (rate, p) in rates.iter_mut().zip(pa.outcomes.iter()) {
+            *rate += p / team.lineup.len() as f64;
+        }
+    }
+    let rate = |outcome: PaOutcome| rates[outcome.index()];
+
+    let walks = rate(PaOutcome::Walk);
+    let home_runs = rate(PaOutcome::HomeRun);
+    let hits = home_runs + rate(PaOutcome::Quadruple) + rate(PaOutcome::Triple) + rate(PaOutcome::Double) + rate(PaOutcome::Single);
+    let total_bases = 4.0 * (home_runs + rate(PaOutcome::Quadruple)) + 3.0 * rate(PaOutcome::Triple) + 2.0 * rate(PaOutcome::Double) + rate(PaOutcome::Single);
+    //a lineup that can't make outs would score forever, keep it finite
+    let outs = (rate(PaOutcome::Strikeout) + rate(PaOutcome::Flyout) + rate(PaOutcome::GroundOut)).max(0.01);
+
+    //david smyth's base runs: baserunners, times the share of them that score, plus home runs
+    let runners = hits + walks - home_runs;
+    let advancement = (1.4 * total_bases - 0.6 * hits - 3.0 * home_runs + 0.1 * walks) * 1.02;
+    let runs_per_pa = runners * advancement.max(0.0) / (advancement.max(0.0) + outs) + home_runs;
+
+    //runs are worth what they're worth on average over nine innings
+    let value = (1..=9).map(|inning| game.get_run_value_in(inning)).sum::<f64>() / 9.0;
+    runs_per_pa * OUTS_PER_GAME / outs * value
+}
+
+pub fn home_odds(game: &Game, world: &World) -> f64 {
+    //home field advantage and the like already count for something
+    let away = (expected_runs(game, world, true) + game.scoreboard.away_team.score).max(0.0);
+    let home = (expected_runs(game, world, false) + game.scoreboard.home_team.score).max(0.0);
+    if away + home == 0.0 {
+        return 0.5;
+    }
+    let home_weight = home.powf(PYTHAGOREAN_EXPONENT);
+    home_weight / (home_weight + away.powf(PYTHAGOREAN_EXPONENT))
+}
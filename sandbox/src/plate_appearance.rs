@@ -0,0 +1,275 @@
+use crate::entities::{Player, World};
+use crate::formulas;
+use crate::mods::Mod;
+use crate::ruleset::Ruleset;
+use crate::{Game, MultiplierData};
+
+//the exact outcome distribution of a plate appearance, following do_pitch roll for roll.
+//only the base pitch is covered: charm, mild pitches, steals, base instincts and the like come from other plugins
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaOutcome {
+    Walk,
+    Strikeout,
+    HomeRun,
+    Quadruple,
+    Triple,
+    Double,
+    Single,
+    Flyout,
+    GroundOut,
+    DoublePlay,
+    FieldersChoice,
+}
+
+impl PaOutcome {
+    pub const ALL: [PaOutcome; 11] = [
+        PaOutcome::Walk,
+        PaOutcome::Strikeout,
+        PaOutcome::HomeRun,
+        PaOutcome::Quadruple,
+        PaOutcome::Triple,
+        PaOutcome::Double,
+        PaOutcome::Single,
+        PaOutcome::Flyout,
+        PaOutcome::GroundOut,
+        PaOutcome::DoublePlay,
+        PaOutcome::FieldersChoice,
+    ];
+
+    pub fn index(&self) -> usize {
+        PaOutcome::ALL.iter().position(|o| o == self).unwrap()
+    }
+}
+
+//the parts of the game state a plate appearance depends on
+#[derive(Clone, Copy, Debug)]
+pub struct Situation {
+    pub outs: i16,
+    pub runners_empty: bool,
+    pub first_occupied: bool,
+    pub bases: u8,
+    pub max_balls: i16,
+    pub max_strikes: i16,
+    pub o_no: bool,
+}
+
+impl Situation {
+    //needs a batter up, like get_max_strikes
+    pub fn from_game(game: &Game, world: &World) -> Situation {
+        Situation {
+            outs: game.outs,
+            runners_empty: game.runners.empty(),
+            first_occupied: game.runners.occupied(0),
+            bases: game.get_bases(world),
+            max_balls: game.get_max_balls(world),
+            max_strikes: game.get_max_strikes(world),
+            o_no: world.team(game.scoreboard.batting_team().id).mods.has(Mod::ONo),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PlateAppearance {
+    pub max_balls: i16,
+    pub max_strikes: i16,
+    //indexed like PaOutcome::ALL
+    pub outcomes: [f64; 11],
+    //one row per count, then one absorbing row per outcome. see `count` and `outcome` for the indices
+    pub transitions: Vec<Vec<f64>>,
+    pub expected_pitches: f64,
+}
+
+impl PlateAppearance {
+    pub fn count(&self, balls: i16, strikes: i16) -> usize {
+        (balls * self.max_strikes + strikes) as usize
+    }
+
+    pub fn outcome(&self, outcome: PaOutcome) -> usize {
+        (self.max_balls * self.max_strikes) as usize + outcome.index()
+    }
+
+    pub fn probability(&self, outcome: PaOutcome) -> f64 {
+        self.outcomes[outcome.index()]
+    }
+
+    //the outcome distribution and expected number of pitches left from a given count
+    fn absorb(&self, balls: i16, strikes: i16) -> ([f64; 11], f64) {
+        let counts = (self.max_balls * self.max_strikes) as usize;
+        let size = self.transitions.len();
+        let (max_balls, max_strikes) = (self.max_balls, self.max_strikes);
+        let mut outcomes = [0.0; 11];
+        let mut pitches = 0.0;
+        //counts only ever go up or stay put, so going through them in order of balls + strikes
+        //means every count is finished before anything it leads to
+        let mut reach = vec![0.0; counts];
+        reach[self.count(balls, strikes)] = 1.0;
+        for total in 0..(max_balls + max_strikes - 1) {
+            for balls in 0..max_balls {
+                let strikes = total - balls;
+                if strikes < 0 || strikes >= max_strikes {
+                    continue;
+                }
+                let from = self.count(balls, strikes);
+                let stay = self.transitions[from][from];
+                if stay >= 1.0 {
+                    continue;
+                }
+                //staying put just repeats the pitch, so every visit lasts 1 / (1 - stay) pitches on average
+                pitches += reach[from] / (1.0 - stay);
+                for to in 0..size {
+                    if to == from {
+                        continue;
+                    }
+                    let p = reach[from] * self.transitions[from][to] / (1.0 - stay);
+                    if to < counts {
+                        reach[to] += p;
+                    } else {
+                        outcomes[to - counts] += p;
+                    }
+                }
+            }
+        }
+        (outcomes, pitches)
+    }
+
+    //for a plate appearance that's already underway
+    pub fn from_count(&self, balls: i16, strikes: i16) -> [f64; 11] {
+        self.absorb(balls, strikes).0
+    }
+}
+
+fn chance(threshold: f64) -> f64 {
+    threshold.clamp(0.0, 1.0)
+}
+
+//what happens once the ball is in play. it doesn't depend on the count, so it only gets worked out once
+fn in_play(pitcher: &Player, batter: &Player, fielders: &[&Player], situation: &Situation, ruleset: &Ruleset, multiplier_data: &MultiplierData) -> [f64; 11] {
+    let mut outcomes = [0.0; 11];
+    let pick = 1.0 / fielders.len() as f64;
+    let fly = chance(formulas::fly_threshold(batter, pitcher, ruleset, multiplier_data));
+    for out_defender in fielders {
+        let out = 1.0 - chance(formulas::out_threshold(pitcher, batter, out_defender, ruleset, multiplier_data));
+        outcomes[PaOutcome::Flyout.index()] += pick * out * fly;
+        let ground = pick * out * (1.0 - fly);
+        if situation.outs == 2 || situation.runners_empty || !situation.first_occupied {
+            outcomes[PaOutcome::GroundOut.index()] += ground;
+        } else {
+            let double_play = chance(formulas::double_play_threshold(batter, pitcher, out_defender, ruleset, multiplier_data));
+            let sacrifice = chance(formulas::groundout_sacrifice_threshold(batter, ruleset, multiplier_data));
+            outcomes[PaOutcome::DoublePlay.index()] += ground * double_play;
+            outcomes[PaOutcome::GroundOut.index()] += ground * (1.0 - double_play) * sacrifice;
+            outcomes[PaOutcome::FieldersChoice.index()] += ground * (1.0 - double_play) * (1.0 - sacrifice);
+        }
+    }
+
+    let hit = 1.0 - outcomes.iter().sum::<f64>();
+    let hr = chance(formulas::hr_threshold(pitcher, batter, ruleset, multiplier_data));
+    outcomes[PaOutcome::HomeRun.index()] = hit * hr;
+    for hit_defender in fielders {
+        let share = hit * (1.0 - hr) * pick;
+        let quadruple = if situation.bases == 5 {
+            chance(formulas::quadruple_threshold(pitcher, batter, hit_defender, ruleset, multiplier_data))
+        } else {
+            0.0
+        };
+        let triple = chance(formulas::triple_threshold(pitcher, batter, hit_defender, ruleset, multiplier_data));
+        let double = chance(formulas::double_threshold(pitcher, batter, hit_defender, ruleset, multiplier_data));
+        outcomes[PaOutcome::Quadruple.index()] += share * quadruple;
+        outcomes[PaOutcome::Triple.index()] += share * (1.0 - quadruple) * triple;
+        outcomes[PaOutcome::Double.index()] += share * (1.0 - quadruple) * (1.0 - triple) * double;
+        outcomes[PaOutcome::Single.index()] += share * (1.0 - quadruple) * (1.0 - triple) * (1.0 - double);
+    }
+    outcomes
+}
+
+pub fn plate_appearance(pitcher: &Player, batter: &Player, fielders: &[&Player], situation: &Situation, ruleset: &Ruleset, multiplier_data: &MultiplierData) -> PlateAppearance {
+    let max_balls = situation.max_balls;
+    let max_strikes = situation.max_strikes;
+    let counts = (max_balls * max_strikes) as usize;
+    let size = counts + PaOutcome::ALL.len();
+    let mut pa = PlateAppearance {
+        max_balls,
+        max_strikes,
+        outcomes: [0.0; 11],
+        transitions: vec![vec![0.0; size]; size],
+        expected_pitches: 0.0,
+    };
+    for i in counts..size {
+        pa.transitions[i][i] = 1.0;
+    }
+
+    let fair = in_play(pitcher, batter, fielders, situation, ruleset, multiplier_data);
+    let walk = pa.outcome(PaOutcome::Walk);
+    let strikeout = pa.outcome(PaOutcome::Strikeout);
+
+    for balls in 0..max_balls {
+        for strikes in 0..max_strikes {
+            let from = pa.count(balls, strikes);
+            let flinching = strikes == 0 && batter.mods.has(Mod::Flinch);
+            let strike = chance(formulas::strike_threshold(pitcher, batter, flinching, ruleset, multiplier_data));
+            let (swing_strike, swing_ball) = if flinching {
+                (0.0, 0.0)
+            } else {
+                (
+                    chance(formulas::swing_threshold(pitcher, batter, true, ruleset, multiplier_data)),
+                    chance(formulas::swing_threshold(pitcher, batter, false, ruleset, multiplier_data)),
+                )
+            };
+            let contact_strike = chance(formulas::contact_threshold(pitcher, batter, true, ruleset, multiplier_data));
+            let contact_ball = chance(formulas::contact_threshold(pitcher, batter, false, ruleset, multiplier_data));
+            let foul = chance(formulas::foul_threshold(pitcher, batter, ruleset, multiplier_data));
+
+            let ball = (1.0 - strike) * (1.0 - swing_ball);
+            let looking = strike * (1.0 - swing_strike);
+            let swinging = strike * swing_strike * (1.0 - contact_strike) + (1.0 - strike) * swing_ball * (1.0 - contact_ball);
+            let contact = strike * swing_strike * contact_strike + (1.0 - strike) * swing_ball * contact_ball;
+
+            let row = &mut pa.transitions[from];
+            if balls + 1 < max_balls {
+                row[(balls + 1) as usize * max_strikes as usize + strikes as usize] += ball;
+            } else {
+                row[walk] += ball;
+            }
+            let next_strike = (balls * max_strikes + strikes + 1) as usize;
+            let last_strike = strikes + 1 >= max_strikes;
+            //a foul with two strikes leaves the count where it is
+            let foul_to = if last_strike { from } else { next_strike };
+            if last_strike {
+                row[strikeout] += swinging;
+                if situation.o_no && balls == 0 {
+                    row[foul_to] += looking;
+                } else {
+                    row[strikeout] += looking;
+                }
+            } else {
+                row[next_strike] += swinging + looking;
+            }
+            row[foul_to] += contact * foul;
+            for (i, p) in fair.iter().enumerate() {
+                row[counts + i] += contact * (1.0 - foul) * p;
+            }
+        }
+    }
+
+    let (outcomes, expected_pitches) = pa.absorb(0, 0);
+    pa.outcomes = outcomes;
+    pa.expected_pitches = expected_pitches;
+    pa
+}
+
+//the plate appearance for whoever is up right now
+pub fn current(game: &Game, world: &World) -> PlateAppearance {
+    let pitcher = world.player(game.pitcher());
+    let batter = world.player(game.batter().unwrap());
+    let fielding_team = world.team(game.scoreboard.pitching_team().id);
+    let fielders: Vec<&Player> = fielding_team.lineup.iter().map(|&id| world.player(id)).collect();
+    plate_appearance(
+        pitcher,
+        batter,
+        &fielders,
+        &Situation::from_game(game, world),
+        &world.ruleset,
+        &game.compute_multiplier_data(world),
+    )
+}
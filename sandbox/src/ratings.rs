@@ -0,0 +1,138 @@
+use std::fmt;
+
+use crate::entities::{Player, PlayerAttr, Team, World};
+use crate::formulas::item;
+use crate::ruleset::{Era, Ruleset};
+use uuid::Uuid;
+
+//the star ratings blaseball showed on player pages, as ratings from 0 to about 1. stars are five times that
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ratings {
+    pub batting: f64,
+    pub pitching: f64,
+    pub baserunning: f64,
+    pub defense: f64,
+}
+
+impl Ratings {
+    pub fn stars(rating: f64) -> f64 {
+        5.0 * rating
+    }
+
+    //which stat goes into which rating with what weight. the formulas didn't change in the eras sandbox supports
+    fn weights(ruleset: &Ruleset) -> [(PlayerAttr, f64); 23] {
+        match ruleset.era {
+            Era::S11 | Era::S12 | Era::S13 | Era::S14 => [
+                (PlayerAttr::Tragicness, 0.01),
+                (PlayerAttr::Patheticism, 0.05),
+                (PlayerAttr::Thwackability, 0.35),
+                (PlayerAttr::Divinity, 0.35),
+                (PlayerAttr::Moxie, 0.075),
+                (PlayerAttr::Musclitude, 0.075),
+                (PlayerAttr::Martyrdom, 0.02),
+                (PlayerAttr::Shakespearianism, 0.1),
+                (PlayerAttr::Suppression, 0.25),
+                (PlayerAttr::Unthwackability, 0.5),
+                (PlayerAttr::Coldness, 0.025),
+                (PlayerAttr::Overpowerment, 0.15),
+                (PlayerAttr::Ruthlessness, 0.4),
+                (PlayerAttr::Laserlikeness, 0.5),
+                (PlayerAttr::BaseThirst, 0.1),
+                (PlayerAttr::Continuation, 0.1),
+                (PlayerAttr::GroundFriction, 0.1),
+                (PlayerAttr::Indulgence, 0.1),
+                (PlayerAttr::Omniscience, 0.2),
+                (PlayerAttr::Tenaciousness, 0.2),
+                (PlayerAttr::Watchfulness, 0.1),
+                (PlayerAttr::Anticapitalism, 0.1),
+                (PlayerAttr::Chasiness, 0.1),
+            ],
+        }
+    }
+
+    //a weighted geometric mean of sorts, negative stats count as 1 - stat
+    fn from_stats<F: Fn(PlayerAttr) -> f64>(ruleset: &Ruleset, stat: F) -> Ratings {
+        let mut ratings = Ratings { batting: 1.0, pitching: 1.0, baserunning: 1.0, defense: 1.0 };
+        for (attr, weight) in Ratings::weights(ruleset) {
+            let value = if attr.is_negative() { 1.0 - stat(attr) } else { stat(attr) };
+            let rating = if attr.is_batting() {
+                &mut ratings.batting
+            } else if attr.is_pitching() {
+                &mut ratings.pitching
+            } else if attr.is_running() {
+                &mut ratings.baserunning
+            } else {
+                &mut ratings.defense
+            };
+            *rating *= value.max(0.0).powf(weight);
+        }
+        ratings
+    }
+
+    //what the player page showed
+    pub fn new(player: &Player, ruleset: &Ruleset) -> Ratings {
+        Ratings::from_stats(ruleset, |attr| player.get(attr))
+    }
+
+    //with the legendary item's boosts, which the player page left out
+    pub fn with_item(player: &Player, ruleset: &Ruleset) -> Ratings {
+        Ratings::from_stats(ruleset, |attr| player.get(attr) + item(attr, &player.legendary_item))
+    }
+
+    //with the item and the day's vibes, the way most formulas see the player
+    pub fn with_vibes(player: &Player, ruleset: &Ruleset, day: usize) -> Ratings {
+        let vibes = 1.0 + 0.2 * player.vibes(day);
+        Ratings::from_stats(ruleset, |attr| {
+            let stat = player.get(attr) + item(attr, &player.legendary_item);
+            if attr.is_negative() {
+                1.0 - (1.0 - stat) * vibes
+            } else {
+                stat * vibes
+            }
+        })
+    }
+}
+
+impl fmt::Display for Ratings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "batting {}, pitching {}, baserunning {}, defense {}",
+            Stars(self.batting), Stars(self.pitching), Stars(self.baserunning), Stars(self.defense)
+        )
+    }
+}
+
+//a rating shown as stars, rounded to the nearest half star like the site did
+pub struct Stars(pub f64);
+
+impl fmt::Display for Stars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let halves = (2.0 * Ratings::stars(self.0)).round().max(0.0) as usize;
+        write!(f, "{}{}", "★".repeat(halves / 2), if halves % 2 == 1 { "½" } else { "" })?;
+        if halves == 0 {
+            write!(f, "-")?;
+        }
+        write!(f, " ({:.1})", Ratings::stars(self.0))
+    }
+}
+
+impl Team {
+    //batting, baserunning and defense averaged over the lineup, pitching over the rotation
+    pub fn ratings<F: Fn(&Player) -> Ratings>(&self, world: &World, rate: F) -> Ratings {
+        let average = |ids: &Vec<Uuid>, get: fn(&Ratings) -> f64| {
+            if ids.is_empty() {
+                0.0
+            } else {
+                ids.iter().map(|&id| get(&rate(world.player(id)))).sum::<f64>() / ids.len() as f64
+            }
+        };
+        Ratings {
+            batting: average(&self.lineup, |r| r.batting),
+            pitching: average(&self.rotation, |r| r.pitching),
+            baserunning: average(&self.lineup, |r| r.baserunning),
+            defense: average(&self.lineup, |r| r.defense),
+        }
+    }
+}
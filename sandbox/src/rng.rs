@@ -1,3 +1,13 @@
+//anything that hands out rolls in [0, 1). the sim is generic over it so a replay can feed it recorded rolls
+//while the xorshift in Rng stays as cheap as it can be
+pub trait Roll {
+    fn next(&mut self) -> f64;
+
+    fn index(&mut self, len: usize) -> usize {
+        (self.next() * len as f64).floor() as usize
+    }
+}
+
 pub struct Rng {
     s0: u64,
     s1: u64,
@@ -29,3 +39,47 @@ impl Rng {
         (self.next() * len as f64).floor() as usize
     }
 }
+
+impl Roll for Rng {
+    fn next(&mut self) -> f64 {
+        Rng::next(self)
+    }
+}
+
+//plays back a roll stream, e.g. one logged by resim.
+//past the end it hands out zeroes and keeps counting, so the replay can say where it ran out instead of panicking
+pub struct Replay {
+    rolls: Vec<f64>,
+    position: usize,
+}
+
+impl Replay {
+    pub fn new(rolls: Vec<f64>) -> Replay {
+        Replay { rolls, position: 0 }
+    }
+
+    //how many rolls have been asked for so far, including any past the end
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn recorded(&self) -> usize {
+        self.rolls.len()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.rolls.len().saturating_sub(self.position)
+    }
+
+    pub fn exhausted(&self) -> bool {
+        self.position > self.rolls.len()
+    }
+}
+
+impl Roll for Replay {
+    fn next(&mut self) -> f64 {
+        let roll = self.rolls.get(self.position).copied().unwrap_or(0.0);
+        self.position += 1;
+        roll
+    }
+}
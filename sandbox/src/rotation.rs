@@ -0,0 +1,50 @@
+use uuid::Uuid;
+
+use crate::{entities::{Team, World}, mods::Mod};
+
+//who starts each game. every team has a rotation slot pointing at their next starter, carrying on
+//through the season and into the postseason, one spot per game. a Shelled starter's game goes to
+//whoever's in the spot before theirs, the pitcher from the day before, which is what sandbox has always done.
+//nobody's checked what the real game did when a shelled pitcher came up, or with Elsewhere ones at all
+
+//the pitcher the slot points at, whether they can pitch or not
+pub fn slotted(world: &World, team: Uuid) -> Uuid {
+    let team = world.team(team);
+    team.rotation[team.rotation_slot % team.rotation.len()]
+}
+
+//today's starter. the spot before the first one is the last one, so opening day doesn't underflow
+pub fn starter(world: &World, team: Uuid) -> Uuid {
+    let pitcher = slotted(world, team);
+    if world.player(pitcher).mods.has(Mod::Shelled) {
+        let t = world.team(team);
+        let len = t.rotation.len();
+        t.rotation[(t.rotation_slot % len + len - 1) % len]
+    } else {
+        pitcher
+    }
+}
+
+//the team finished a game, whoever ended up pitching it
+pub fn advance(world: &mut World, team: Uuid) {
+    let team = world.team_mut(team);
+    team.rotation_slot = (team.rotation_slot + 1) % team.rotation.len();
+}
+
+//a pitcher swapped out for someone else (incinerated, fed back, night shifted), who takes their spot.
+//false if they weren't in the rotation, which a replayed game starting someone else can do
+pub fn replace(team: &mut Team, pitcher: Uuid, replacement: Uuid) -> bool {
+    match team.rotation.iter().position(|&p| p == pitcher) {
+        Some(idx) => {
+            team.rotation[idx] = replacement;
+            true
+        },
+        None => false
+    }
+}
+
+//a reverb reordering the rotation. the slot stays put, so whoever got shuffled into it is up next
+pub fn shuffle(team: &mut Team, rotation: &[Uuid]) {
+    debug_assert_eq!(rotation.len(), team.rotation.len());
+    team.rotation.copy_from_slice(rotation);
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+
+use crate::constants::Constants;
+
+//formula eras, numbered by 0-indexed season like everything else.
+//a few formulas are shared between eras, but the thresholds around them changed every season
+//later seasons are missing because we don't know their contact and strike formulas yet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Era {
+    S11,
+    S12,
+    S13,
+    S14,
+}
+
+//every plugin sim.rs knows about, in the order they get to tick
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluginKind {
+    Pregame,
+    InningState,
+    InningEvent,
+    BatterState,
+    Weather,
+    Elsewhere,
+    Party,
+    Flooding,
+    Mod,
+    Stealing,
+    Base,
+}
+
+#[derive(Clone, Debug)]
+pub enum RulesetError {
+    UnsupportedSeason(u8),
+    //an era we have formulas for, but not everything else a season needs
+    Incomplete { season: u8, missing: &'static str },
+}
+
+impl fmt::Display for RulesetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesetError::UnsupportedSeason(season) => write!(f, "season {} (0-indexed) isn't supported, sandbox only has formulas for seasons 11 to 14", season),
+            RulesetError::Incomplete { season, missing } => write!(f, "season {} (0-indexed) isn't supported yet, its {} hasn't been worked out", season, missing)
+        }
+    }
+}
+
+//weights for Weather::generate, in the order of its weather list. flooding only gets a weight from `late_from` on
+#[derive(Clone, Debug)]
+pub struct WeatherTable {
+    pub early: Vec<usize>,
+    pub late: Vec<usize>,
+    pub late_from: usize,
+}
+
+impl WeatherTable {
+    fn new(era: Era) -> Option<WeatherTable> {
+        match era {
+            Era::S11 => Some(WeatherTable {
+                early: vec![50, 20, 20, 35, 20, 20, 20, 50, 2, 2, 1],
+                late: vec![50, 20, 20, 35, 20, 20, 20, 50, 2, 2, 1, 200],
+                late_from: 72,
+            }),
+            //salmon and polarity came in over these seasons, so season 11's table would be wrong for them
+            Era::S12 | Era::S13 | Era::S14 => None
+        }
+    }
+}
+
+//which plugins tick, in order
+fn plugins(era: Era) -> Option<Vec<PluginKind>> {
+    match era {
+        Era::S11 => Some(vec![
+            PluginKind::Pregame,
+            PluginKind::InningState,
+            PluginKind::InningEvent,
+            PluginKind::BatterState,
+            PluginKind::Weather,
+            PluginKind::Elsewhere,
+            PluginKind::Party,
+            PluginKind::Flooding,
+            PluginKind::Mod,
+            PluginKind::Stealing,
+            PluginKind::Base,
+        ]),
+        //these have their own weathers and mods to tick, which nobody's checked the order of
+        Era::S12 | Era::S13 | Era::S14 => None
+    }
+}
+
+//everything about a season's rules: which formulas, which weathers, which plugins and the estimated constants.
+//a world can only be made from a ruleset that exists, so nothing has to panic mid-game over the season number
+#[derive(Clone, Debug)]
+pub struct Ruleset {
+    pub season: u8,
+    pub era: Era,
+    pub plugins: Vec<PluginKind>,
+    pub weather: WeatherTable,
+    pub constants: Constants,
+    //regulation length, see game_end.rs. every era we have plays nine,
+    //a decree that changes how long games are would set it here
+    pub innings: i16,
+    //whether the home team taking the lead in the bottom of the last inning or later ends the game on the spot,
+    //rather than the half playing out. off for every era we have: nothing we've found shows seasons 11 to 14
+    //walking off, and the sim has always played the half out to three outs
+    pub walk_offs: bool,
+    //whether the home team leading after the top of the last inning shames the away team
+    pub shame: bool,
+}
+
+impl Ruleset {
+    pub fn new(season: u8) -> Result<Ruleset, RulesetError> {
+        let era = match season {
+            11 => Era::S11,
+            12 => Era::S12,
+            13 => Era::S13,
+            14 => Era::S14,
+            _ => return Err(RulesetError::UnsupportedSeason(season))
+        };
+        //the formulas go back further than the rest, so a season can still be missing pieces
+        let plugins = plugins(era).ok_or(RulesetError::Incomplete { season, missing: "plugin order" })?;
+        let weather = WeatherTable::new(era).ok_or(RulesetError::Incomplete { season, missing: "weather table" })?;
+        Ok(Ruleset {
+            season,
+            era,
+            plugins,
+            weather,
+            constants: Constants::new(season),
+            innings: match era {
+                Era::S11 | Era::S12 | Era::S13 | Era::S14 => 9
+            },
+            walk_offs: match era {
+                Era::S11 | Era::S12 | Era::S13 | Era::S14 => false
+            },
+            shame: match era {
+                Era::S11 | Era::S12 | Era::S13 | Era::S14 => true
+            },
+        })
+    }
+
+    //weights for Weather::generate, in the order of its weather list
+    pub fn weather_weights(&self, day: usize) -> &[usize] {
+        if day < self.weather.late_from {
+            &self.weather.early
+        } else {
+            &self.weather.late
+        }
+    }
+
+    pub fn drain_threshold(&self, fort: f64) -> f64 {
+        //from season 16 on: 0.00125 - 0.00125 * fort
+        match self.era {
+            Era::S11 | Era::S12 | Era::S13 | Era::S14 => 0.00065 - 0.001 * fort
+        }
+    }
+
+    pub fn charm_threshold(&self, myst: f64) -> f64 {
+        //season 18: 0.014 + 0.006 * myst
+        match self.era {
+            Era::S11 | Era::S12 | Era::S13 | Era::S14 => 0.015 + 0.02 * myst
+        }
+    }
+
+    pub fn flooding_threshold(&self, fort: f64) -> f64 {
+        //seasons 14-16 carried on with the season 14 threshold; 17: 0.015 - 0.012 * fort; 18-23: 0.016 - 0.012 * fort
+        match self.era {
+            Era::S11 | Era::S12 | Era::S13 => 0.019 - 0.02 * fort,
+            Era::S14 => 0.013 - 0.012 * fort
+        }
+    }
+
+    pub fn elsewhere_return_threshold(&self) -> f64 {
+        //13-17: 0.0004; 18-23: 0.00035
+        match self.era {
+            Era::S11 => 0.001,
+            Era::S12 => 0.000575,
+            Era::S13 | Era::S14 => 0.0004
+        }
+    }
+
+    pub fn unscatter_threshold(&self) -> f64 {
+        //14-16: 0.0004; 17-19: 0.00042; 20-21: 0.000485; 22-23: 0.000495
+        match self.era {
+            Era::S11 | Era::S12 => 0.00061,
+            Era::S13 => 0.0005,
+            Era::S14 => 0.0004
+        }
+    }
+}
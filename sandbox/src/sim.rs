@@ -1,37 +1,26 @@
 use uuid::Uuid;
 
-use crate::{entities::{World, Player}, events::Event, formulas, mods::{Mod, Mods}, rng::Rng, Game, Weather};
+use crate::{entities::{AttrCategory, Boosts, PlayerAttr, World, Player}, events::Event, formulas, game_end, mods::{Mod, Mods}, rng::{Rng, Roll}, ruleset::PluginKind, Game, GameTeam, Weather};
 
-pub trait Plugin {
-    fn tick(&self, _game: &Game, _world: &World, _rng: &mut Rng) -> Option<Event> {
+pub trait Plugin<R: Roll> {
+    fn tick(&self, _game: &Game, _world: &World, _rng: &mut R) -> Option<Event> {
         None
     }
 }
 
-pub struct Sim<'a> {
-    plugins: Vec<Box<dyn Plugin>>,
+//rolls come from an Rng, except when replaying recorded ones
+pub struct Sim<'a, R: Roll + 'static = Rng> {
+    plugins: Vec<&'static dyn Plugin<R>>,
     pub world: &'a mut World,
-    pub rng: &'a mut Rng,
+    pub rng: &'a mut R,
 }
 
-impl<'a> Sim<'a> {
-    pub fn new(world: &'a mut World, rng: &'a mut Rng) -> Sim<'a> {
+impl<'a, R: Roll + 'static> Sim<'a, R> {
+    pub fn new(world: &'a mut World, rng: &'a mut R) -> Sim<'a, R> {
         Sim {
+            plugins: world.ruleset.plugins.iter().map(|&kind| plugin(kind)).collect(),
             world,
             rng,
-            plugins: vec![
-                Box::new(PregamePlugin),
-                Box::new(InningStatePlugin),
-                Box::new(InningEventPlugin),
-                Box::new(BatterStatePlugin),
-                Box::new(WeatherPlugin),
-                Box::new(ElsewherePlugin),
-                Box::new(PartyPlugin),
-                Box::new(FloodingPlugin),
-                Box::new(ModPlugin),
-                Box::new(StealingPlugin),
-                Box::new(BasePlugin),
-            ],
         }
     }
     pub fn next(&mut self, game: &Game) -> Event {
@@ -45,6 +34,24 @@ impl<'a> Sim<'a> {
     }
 }
 
+//the plugins don't hold any state, so there's no need to box them for every game
+fn plugin<R: Roll + 'static>(kind: PluginKind) -> &'static dyn Plugin<R> {
+    match kind {
+        PluginKind::Pregame => &PregamePlugin,
+        PluginKind::InningState => &InningStatePlugin,
+        PluginKind::InningEvent => &InningEventPlugin,
+        PluginKind::BatterState => &BatterStatePlugin,
+        PluginKind::Weather => &WeatherPlugin,
+        PluginKind::Elsewhere => &ElsewherePlugin,
+        PluginKind::Party => &PartyPlugin,
+        PluginKind::Flooding => &FloodingPlugin,
+        PluginKind::Mod => &ModPlugin,
+        PluginKind::Stealing => &StealingPlugin,
+        PluginKind::Base => &BasePlugin,
+    }
+}
+
+//advancing runners are a mask over game.runners, bit i for the i-th runner
 enum PitchOutcome {
     Ball,
     StrikeSwinging,
@@ -52,24 +59,24 @@ enum PitchOutcome {
     Foul,
     GroundOut {
         fielder: Uuid,
-        advancing_runners: Vec<Uuid>
+        advancing_runners: u8
     },
     Flyout { 
         fielder: Uuid,
-        advancing_runners: Vec<Uuid>
+        advancing_runners: u8
     },
     DoublePlay { runner_out: u8 },
     FieldersChoice { runner_out: u8 },
     HomeRun,
-    Triple { advancing_runners: Vec<Uuid> },
-    Double { advancing_runners: Vec<Uuid> },
-    Single { advancing_runners: Vec<Uuid> },
-    Quadruple { advancing_runners: Vec<Uuid> }
+    Triple { advancing_runners: u8 },
+    Double { advancing_runners: u8 },
+    Single { advancing_runners: u8 },
+    Quadruple { advancing_runners: u8 }
 }
 
 struct BasePlugin;
-impl Plugin for BasePlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for BasePlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
         let max_balls = game.get_max_balls(world);
         let max_strikes = game.get_max_strikes(world);
         // let max_outs = 3;
@@ -81,7 +88,7 @@ impl Plugin for BasePlugin {
                 if (game.balls + 1) < max_balls {
                     Event::Ball
                 } else {
-                    if world.player(game.batter().unwrap()).mods.has(Mod::BaseInstincts) && rng.next() < 0.2 {
+                    if world.player(game.batter().unwrap()).mods.has(Mod::BaseInstincts) && rng.next() < world.ruleset.constants.base_instincts {
                         Event::InstinctWalk { third: rng.next() * rng.next() < 0.5 }
                     } else {
                         Event::Walk
@@ -109,88 +116,90 @@ impl Plugin for BasePlugin {
             PitchOutcome::Foul => Event::Foul,
             PitchOutcome::GroundOut { fielder, advancing_runners } => {
                 let mut new_runners = game.runners.clone();
-                new_runners.advance_if(|runner| advancing_runners.contains(&runner.id));
+                let scored = new_runners.advance_marked(0, advancing_runners);
                 Event::GroundOut {
                     fielder,
                     runners_after: new_runners,
+                    scored,
                 }
             },
             PitchOutcome::Flyout { fielder, advancing_runners } => {
                 let mut new_runners = game.runners.clone();
-                new_runners.advance_if(|runner| advancing_runners.contains(&runner.id));
+                let scored = new_runners.advance_marked(0, advancing_runners);
                 Event::Flyout {
                     fielder,
                     runners_after: new_runners,
+                    scored,
                 }
             },
             PitchOutcome::DoublePlay { runner_out } => {
                 let mut new_runners = game.runners.clone();
                 new_runners.remove(runner_out);
-                new_runners.advance_all(1);
+                let scored = new_runners.advance_all(1);
                 Event::DoublePlay {
-                    runners_after: new_runners
+                    runners_after: new_runners,
+                    scored,
                 }
             },
             PitchOutcome::FieldersChoice { runner_out } => {
                 let mut new_runners = game.runners.clone();
                 new_runners.remove(runner_out);
-                new_runners.advance_all(1);
+                let scored = new_runners.advance_all(1);
                 Event::FieldersChoice {
-                    runners_after: new_runners
+                    runners_after: new_runners,
+                    scored,
                 }
             },
 
             PitchOutcome::HomeRun => Event::HomeRun,
 
-            // todo: there may be a subtle bug here since we don't sweep the runners after the forced advance
-            // runner [1, 0], double, then we're at [3, 2], 3 *should* get swept and *then* 2 should get to advance to 3...
             PitchOutcome::Triple { advancing_runners } => {
                 let mut new_runners = game.runners.clone();
-                new_runners.advance_all(3);
-                new_runners.advance_if(|runner| advancing_runners.contains(&runner.id));
+                let scored = new_runners.hit(3, advancing_runners, game.batter().unwrap(), game.pitcher());
                 Event::BaseHit {
                     bases: 3,
                     runners_after: new_runners,
+                    scored,
                 }
             },
 
             PitchOutcome::Double { advancing_runners } => {
                 let mut new_runners = game.runners.clone();
-                new_runners.advance_all(2);
-                new_runners.advance_if(|runner| advancing_runners.contains(&runner.id));
+                let scored = new_runners.hit(2, advancing_runners, game.batter().unwrap(), game.pitcher());
                 Event::BaseHit {
                     bases: 2,
                     runners_after: new_runners,
+                    scored,
                 }
             },
 
             PitchOutcome::Single { advancing_runners } => {
                 let mut new_runners = game.runners.clone();
-                new_runners.advance_all(1);
-                new_runners.advance_if(|runner| advancing_runners.contains(&runner.id));
+                let scored = new_runners.hit(1, advancing_runners, game.batter().unwrap(), game.pitcher());
                 Event::BaseHit {
                     bases: 1,
                     runners_after: new_runners,
+                    scored,
                 }
             },
 
             PitchOutcome::Quadruple { advancing_runners }=> {
                 let mut new_runners = game.runners.clone();
-                new_runners.advance_all(4);
-                new_runners.advance_if(|runner| advancing_runners.contains(&runner.id));
+                let scored = new_runners.hit(4, advancing_runners, game.batter().unwrap(), game.pitcher());
                 Event::BaseHit {
                     bases: 4,
                     runners_after: new_runners,
+                    scored,
                 }
             },
         })
     }
 }
 
-fn do_pitch(world: &World, game: &Game, rng: &mut Rng) -> PitchOutcome {
+fn do_pitch<R: Roll>(world: &World, game: &Game, rng: &mut R) -> PitchOutcome {
     let pitcher = world.player(game.pitcher());
     let batter = world.player(game.batter().unwrap());
-    let ruleset = world.season_ruleset; //todo: can we fold this into multiplier_data?
+    let ruleset = &world.ruleset; //todo: can we fold this into multiplier_data?
 
     let is_flinching = game.strikes == 0 && batter.mods.has(Mod::Flinch);
 
@@ -231,20 +240,20 @@ fn do_pitch(world: &World, game: &Game, rng: &mut Rng) -> PitchOutcome {
 
         let is_fly = rng.next() < formulas::fly_threshold(batter, pitcher, ruleset, multiplier_data);
         if is_fly {
-            let mut advancing_runners = Vec::new();
+            let mut advancing_runners = 0u8;
             if game.outs == 2 {
                 return PitchOutcome::Flyout {
                     fielder: fly_defender_id,
                     advancing_runners
                 };
             }
-            for baserunner in game.runners.iter() {
+            for (i, baserunner) in game.runners.iter().enumerate() {
                 let base_from = baserunner.base;
                 let runner_id = baserunner.id.clone();
                 let runner = world.player(runner_id);
 
                 if rng.next() < formulas::flyout_advancement_threshold(runner, base_from, ruleset, multiplier_data) {
-                    advancing_runners.push(runner_id);
+                    advancing_runners |= 1 << i;
                 }
             }
             return PitchOutcome::Flyout {
@@ -254,7 +263,7 @@ fn do_pitch(world: &World, game: &Game, rng: &mut Rng) -> PitchOutcome {
         }
 
         let ground_defender_id = game.pick_fielder(world, rng.next());
-        let mut advancing_runners = Vec::new();
+        let mut advancing_runners = 0u8;
         if game.outs == 2 {
             return PitchOutcome::GroundOut {
                 fielder: ground_defender_id,
@@ -272,11 +281,11 @@ fn do_pitch(world: &World, game: &Game, rng: &mut Rng) -> PitchOutcome {
                 } else {
                     let sac_roll = rng.next();
                     if sac_roll < formulas::groundout_sacrifice_threshold(batter, ruleset, multiplier_data) {
-                        for baserunner in game.runners.iter() {
+                        for (i, baserunner) in game.runners.iter().enumerate() {
                             let runner_id = baserunner.id.clone();
                             let runner = world.player(runner_id);
                             if rng.next() < formulas::groundout_advancement_threshold(runner, out_defender, ruleset, multiplier_data) {
-                                advancing_runners.push(runner_id);
+                                advancing_runners |= 1 << i;
                             }
                         }
                         return PitchOutcome::GroundOut {
@@ -290,11 +299,11 @@ fn do_pitch(world: &World, game: &Game, rng: &mut Rng) -> PitchOutcome {
                     }
                 }
             }
-            for baserunner in game.runners.iter() {
+            for (i, baserunner) in game.runners.iter().enumerate() {
                 let runner_id = baserunner.id.clone();
                 let runner = world.player(runner_id);
                 if rng.next() < formulas::groundout_advancement_threshold(runner, out_defender, ruleset, multiplier_data) {
-                    advancing_runners.push(runner_id);
+                    advancing_runners |= 1 << i;
                 }
             }
         }
@@ -318,13 +327,13 @@ fn do_pitch(world: &World, game: &Game, rng: &mut Rng) -> PitchOutcome {
         quadruple_roll = rng.next();
     }
 
-    let mut advancing_runners = Vec::new();
-    for baserunner in game.runners.iter() {
+    let mut advancing_runners = 0u8;
+    for (i, baserunner) in game.runners.iter().enumerate() {
         let runner_id = baserunner.id.clone();
         let runner = world.player(runner_id);
 
         if rng.next() < formulas::hit_advancement_threshold(runner, hit_defender, ruleset, multiplier_data) {
-            advancing_runners.push(runner_id);
+            advancing_runners |= 1 << i;
         }
     }
 
@@ -351,8 +360,8 @@ fn do_pitch(world: &World, game: &Game, rng: &mut Rng) -> PitchOutcome {
 }
 
 struct BatterStatePlugin;
-impl Plugin for BatterStatePlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for BatterStatePlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
         let batting_team = game.scoreboard.batting_team();
         if game.batter().is_none() {
             let idx = batting_team.batter_index;
@@ -367,7 +376,7 @@ impl Plugin for BatterStatePlugin {
             let inning_begin = !first_batter && game.events.last() == "InningSwitch";
             let prev = if first_batter { team.lineup[0].clone() } else { team.lineup[(idx - 1) % team.lineup.len()].clone() };
             //todo: improve this
-            if !first_batter && !inning_begin && world.player(prev).mods.has(Mod::Reverberating) && rng.next() < 0.2 { //rough estimate
+            if !first_batter && !inning_begin && world.player(prev).mods.has(Mod::Reverberating) && rng.next() < world.ruleset.constants.reverberating {
                 return Some(Event::Reverberating { batter: prev });
             } else if !first_batter && !inning_begin && world.player(prev).mods.has(Mod::Repeating) && (game.events.last() == "BaseHit" || game.events.last() == "HomeRun") {
                 if let Weather::Reverb = game.weather {
@@ -379,7 +388,7 @@ impl Plugin for BatterStatePlugin {
                 return Some(Event::Shelled { batter });
             } else if world.player(batter).mods.has(Mod::Elsewhere) {
                 return Some(Event::Elsewhere { batter });
-            } else if world.player(batter).mods.has(Mod::Haunted) && rng.next() < 0.2 {
+            } else if world.player(batter).mods.has(Mod::Haunted) && rng.next() < world.ruleset.constants.haunted {
                 let inhabit = world.random_hall_player(rng);
                 return Some(Event::Inhabiting { batter, inhabit });
             }
@@ -391,23 +400,15 @@ impl Plugin for BatterStatePlugin {
 }
 
 struct InningStatePlugin;
-impl Plugin for InningStatePlugin {
-    fn tick(&self, game: &Game, _world: &World, _rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for InningStatePlugin {
+    fn tick(&self, game: &Game, world: &World, _rng: &mut R) -> Option<Event> {
+        if game_end::is_over(game, &world.ruleset) {
+            return Some(Event::GameOver);
+        }
         if game.outs < 3 {
             return None;
         }
 
-        let lead = if (game.scoreboard.away_team.score - game.scoreboard.home_team.score).abs() < 0.01 {
-            0
-        } else if game.scoreboard.away_team.score > game.scoreboard.home_team.score {
-            1
-        } else {
-            -1
-        }; // lol floats
-        if game.inning >= 9 && (lead == -1 || !game.scoreboard.top && lead == 1) {
-            return Some(Event::GameOver);
-        }
-
         if game.scoreboard.top {
             Some(Event::InningSwitch {
                 inning: game.inning,
@@ -423,8 +424,8 @@ impl Plugin for InningStatePlugin {
 }
 
 struct StealingPlugin;
-impl Plugin for StealingPlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for StealingPlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
         let steal_defender_id = game.pick_fielder(world, rng.next());
         let steal_defender = world.player(steal_defender_id);
 
@@ -461,74 +462,61 @@ impl Plugin for StealingPlugin {
 }
 
 //exclusion: "all", "current", "playing"
-fn poll_for_mod(game: &Game, world: &World, a_mod: Mod, exclusion: &str) -> Vec<Uuid> {
-    let home_team = &game.scoreboard.home_team;
-    let away_team = &game.scoreboard.away_team;
-
-    //not good that runners.runners is accessed directly
-    let home_lineup = if !game.scoreboard.top && exclusion == "playing" { [vec![game.batter().unwrap()], game.runners.iter().map(|r| r.id).collect()].concat() } else { world.team(home_team.id).lineup.clone() };
-    let home_pitcher = if exclusion != "all" { 
-        if !game.scoreboard.top && exclusion == "playing" {
-            Vec::new()
+//in order: the home lineup (or batter and runners), home pitcher(s), then the same for the away team
+fn poll_for_mod<'a>(game: &'a Game, world: &'a World, a_mod: Mod, exclusion: &str) -> impl Iterator<Item = Uuid> + 'a {
+    let side = |team: &'a GameTeam, batting: bool| {
+        let playing = batting && exclusion == "playing";
+        let lineup: &'a [Uuid] = if playing { &[] } else { &world.team(team.id).lineup };
+        let pitchers: &'a [Uuid] = if exclusion == "all" {
+            &world.team(team.id).rotation
+        } else if playing {
+            &[]
         } else {
-            vec![home_team.pitcher.clone()] 
-        }
-    } else { 
-        world.team(home_team.id).rotation.clone() 
-    };
-    let away_lineup = if game.scoreboard.top && exclusion == "playing" { [vec![game.batter().unwrap()], game.runners.iter().map(|r| r.id).collect()].concat() } else { world.team(away_team.id).lineup.clone() };
-    let away_pitcher = if exclusion != "all" { 
-        if game.scoreboard.top && exclusion == "playing" {
-            Vec::new()
-        } else {
-            vec![away_team.pitcher.clone()] 
-        }
-    } else { 
-        world.team(away_team.id).rotation.clone() 
+            std::slice::from_ref(&team.pitcher)
+        };
+        let on_field = if playing { game.batter() } else { None };
+        on_field.into_iter()
+            .chain(game.runners.iter().filter(move |_| playing).map(|r| r.id))
+            .chain(lineup.iter().copied())
+            .chain(pitchers.iter().copied())
     };
-
-    let mut players = vec![home_lineup, home_pitcher, away_lineup, away_pitcher].concat();
-
-    players.retain(|player| world.player(*player).mods.has(a_mod));
-
-    players
+    let top = game.scoreboard.top;
+    side(&game.scoreboard.home_team, !top)
+        .chain(side(&game.scoreboard.away_team, top))
+        .filter(move |&player| world.player(player).mods.has(a_mod))
 }
 
 struct WeatherPlugin;
-impl Plugin for WeatherPlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for WeatherPlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
         let fort = 0.0;
-        let ruleset = world.season_ruleset;
         match game.weather {
             Weather::Sun => None,
             Weather::Eclipse => {
                 //todo: add fortification
-                let fire_eaters = poll_for_mod(game, world, Mod::FireEater, "playing");
                 let incin_roll = rng.next();
                 //todo: the Fire Eater picker prioritizes unstable players
-                if fire_eaters.len() > 0 {
-                    for fe in fire_eaters {
-                        if rng.next() < 0.002 { //estimate
-                            return Some(Event::FireEater { target: fe });
-                        }
+                for fe in poll_for_mod(game, world, Mod::FireEater, "playing") {
+                    if rng.next() < world.ruleset.constants.fire_eater {
+                        return Some(Event::FireEater { target: fe });
                     }
                 }
                 let target = game.pick_player_weighted(world, rng.next(), |&uuid| !game.runners.contains(uuid), true);
-                let unstable_check = world.player(target).mods.has(Mod::Unstable) && incin_roll < 0.002; //estimate
+                let unstable_check = world.player(target).mods.has(Mod::Unstable) && incin_roll < world.ruleset.constants.unstable_incineration;
                 let regular_check = incin_roll < 0.00045 - 0.0004 * fort;
-                if unstable_check || regular_check { //estimate
+                if unstable_check || regular_check {
                     if world.player(target).mods.has(Mod::Fireproof) || world.team(world.player(target).team.unwrap()).mods.has(Mod::Fireproof) {
                         return Some(Event::Fireproof { target });
                     }
-                    let minimized = poll_for_mod(game, world, Mod::Minimized, "all");
-                    if minimized.len() > 0 {
-                        if minimized.len() > 1 { 
+                    let mut minimized = poll_for_mod(game, world, Mod::Minimized, "all");
+                    if let Some(iffey) = minimized.next() {
+                        if minimized.next().is_some() { 
                             //assuming that there's
                             //no more than one legendary item of each kind
                             //at any point in the sim
                             todo!()
                         } else {
-                            if world.player(target).team.unwrap() == world.player(minimized[0]).team.unwrap() && world.player(minimized[0]).mods.has(Mod::Minimized) {
+                            if world.player(target).team.unwrap() == world.player(iffey).team.unwrap() && world.player(iffey).mods.has(Mod::Minimized) {
                                 return Some(Event::IffeyJr { target });
                             }
                         }
@@ -553,7 +541,7 @@ impl Plugin for WeatherPlugin {
                 }
             },
             Weather::Peanuts => {
-                if rng.next() < 0.000002 { //estimate
+                if rng.next() < world.ruleset.constants.big_peanut {
                     //this is maybe not rng compliant
                     let target = game.pick_player_weighted(world, rng.next(), |&_uuid| true, true); //theory
                     Some(Event::BigPeanut {
@@ -565,7 +553,7 @@ impl Plugin for WeatherPlugin {
                     let target = game.pick_player_weighted(world, rng.next(), |&uuid| !game.runners.contains(uuid), true);
                     Some(Event::Peanut {
                         target,
-                        yummy: false
+                        yummy: world.player(target).peanut_allergy == Some(false)
                     })
                 } else if world.player(game.batter().unwrap()).mods.has(Mod::HoneyRoasted) && rng.next() < 0.0076 {
                     //todo: we don't know
@@ -578,16 +566,13 @@ impl Plugin for WeatherPlugin {
                 }
             },
             Weather::Birds => {
-                //rough estimate
-                if rng.next() < 0.03 {
+                if rng.next() < world.ruleset.constants.birds {
                     return Some(Event::Birds);
                 } //todo: this is definitely not rng accurate
                 
-                let shelled_players = poll_for_mod(game, world, Mod::Shelled, "all");
-                for player in shelled_players {
-                    //estimate, not sure how accurate this is
+                for player in poll_for_mod(game, world, Mod::Shelled, "all") {
                     let shelled_roll = rng.next();
-                    if world.team(world.player(player).team.unwrap()).mods.has(Mod::BirdSeed) && shelled_roll < 0.001 || shelled_roll < 0.00015 { //estimate. lmao at bird seed
+                    if world.team(world.player(player).team.unwrap()).mods.has(Mod::BirdSeed) && shelled_roll < world.ruleset.constants.bird_seed_pecking || shelled_roll < world.ruleset.constants.shelled_pecking { //lmao at bird seed
                         return Some(Event::PeckedFree { player });
                     }
                 }
@@ -657,14 +642,13 @@ impl Plugin for WeatherPlugin {
                 }
             },
             Weather::Reverb => {
-                //estimate
-                if rng.next() < 0.00003 {
+                if rng.next() < world.ruleset.constants.reverb {
                     let reverb_type_roll = rng.next();
-                    let reverb_type = if reverb_type_roll < 0.09 {
+                    let reverb_type = if reverb_type_roll < world.ruleset.constants.reverb_type_0 {
                         0u8
-                    } else if reverb_type_roll < 0.55 {
+                    } else if reverb_type_roll < world.ruleset.constants.reverb_type_1 {
                         1u8
-                    } else if reverb_type_roll < 0.95 {
+                    } else if reverb_type_roll < world.ruleset.constants.reverb_type_2 {
                         2u8
                     } else {
                         3u8
@@ -702,21 +686,17 @@ impl Plugin for WeatherPlugin {
                 }
             },
             Weather::Blooddrain => {
-                let drain_threshold = if ruleset < 16 { 
-                    0.00065 - 0.001 * fort 
-                } else {
-                    0.00125 - 0.00125 * fort
-                };
-                let siphon_threshold = 0.0025;
-                let siphons = poll_for_mod(game, world, Mod::Siphon, "playing");
+                let drain_threshold = world.ruleset.drain_threshold(fort);
+                let siphon_threshold = world.ruleset.constants.siphon;
+                let siphons = poll_for_mod(game, world, Mod::Siphon, "playing").count();
                 let drain_roll = rng.next();
-                if drain_roll < drain_threshold || siphons.len() > 0 && drain_roll < siphon_threshold { //rulesets
+                if drain_roll < drain_threshold || siphons > 0 && drain_roll < siphon_threshold { //rulesets
                     let mut drainer: Uuid;
                     let mut target: Uuid;
                     let siphon = drain_roll > drain_threshold;
                     //siphon code
                     if siphon {
-                        let siphon_player = siphons[rng.index(siphons.len())];
+                        let siphon_player = poll_for_mod(game, world, Mod::Siphon, "playing").nth(rng.index(siphons)).unwrap();
                         let active_target = rng.next() < 0.5;
                         if active_target {
                             target = if siphon_player == game.batter().unwrap() { game.pitcher() } else { game.batter().unwrap() };
@@ -756,17 +736,17 @@ impl Plugin for WeatherPlugin {
                         Some(Event::BlockedDrain { drainer, target })
                     } else {
                         let siphon_effect_roll = if siphon { rng.next() } else { 0.0 };
-                        let siphon_effect = if siphon_effect_roll < 0.35 {
+                        let siphon_effect = if siphon_effect_roll < world.ruleset.constants.siphon_negative {
                             -1
                         } else {
                             if world.player(drainer).team.unwrap() == game.scoreboard.batting_team().id {
-                                if game.outs > 0 && siphon_effect_roll < 0.5 {//wild guesstimates
+                                if game.outs > 0 && siphon_effect_roll < world.ruleset.constants.siphon_batting {
                                     1
                                 } else {
                                     -1
                                 }
                             } else {
-                                if game.balls > 0 && siphon_effect_roll < 0.8 {
+                                if game.balls > 0 && siphon_effect_roll < world.ruleset.constants.siphon_pitching {
                                     2
                                 } else {
                                     0
@@ -776,7 +756,7 @@ impl Plugin for WeatherPlugin {
                         Some(Event::Blooddrain {
                             drainer,
                             target,
-                            stat: (rng.next() * 4.0).floor() as u8,
+                            stat: AttrCategory::DRAINABLE[(rng.next() * 4.0).floor() as usize],
                             siphon,
                             siphon_effect
                         })
@@ -786,18 +766,18 @@ impl Plugin for WeatherPlugin {
                 }
             },
             Weather::Sun2 => {
-                if game.scoreboard.home_team.score > 9.99 { //ugh
+                if game_end::reached_ten(game.scoreboard.home_team.score) {
                     Some(Event::Sun2 { home_team: true })
-                } else if game.scoreboard.away_team.score > 9.99 {
+                } else if game_end::reached_ten(game.scoreboard.away_team.score) {
                     Some(Event::Sun2 { home_team: false })
                 } else {
                     None
                 }
             },
             Weather::BlackHole => {
-                if game.scoreboard.home_team.score > 9.99 {
+                if game_end::reached_ten(game.scoreboard.home_team.score) {
                     Some(Event::BlackHole { home_team: true })
-                } else if game.scoreboard.away_team.score > 9.99 {
+                } else if game_end::reached_ten(game.scoreboard.away_team.score) {
                     Some(Event::BlackHole { home_team: false })
                 } else {
                     None
@@ -829,7 +809,7 @@ impl Plugin for WeatherPlugin {
             },
             Weather::SunPointOne | Weather::SumSun => None,
             Weather::Night => {
-                if rng.next() < 0.01 { //estimate
+                if rng.next() < world.ruleset.constants.night_shift {
                     let batter = rng.next() < 0.5;
                     let shadows = if batter { &world.team(game.scoreboard.batting_team().id).shadows } else { &world.team(game.scoreboard.pitching_team().id).shadows };
                     let replacement_idx = (rng.next() * shadows.len() as f64).floor() as usize;
@@ -844,23 +824,20 @@ impl Plugin for WeatherPlugin {
     }
 }
 
-fn roll_random_boosts(rng: &mut Rng, base: f64, threshold: f64, exclude_press: bool) -> Vec<f64> {
-    let mut boosts: Vec<f64> = Vec::new();
+fn roll_random_boosts<R: Roll>(rng: &mut R, base: f64, threshold: f64, exclude_press: bool) -> Boosts {
     //does Tangled decrease press or cinn???
-    let stat_number = if exclude_press { 25 } else { 26 };
-    for _ in 0..stat_number {
-        boosts.push(base + rng.next() * threshold);
-    }
-    boosts
+    let attrs = PlayerAttr::ALL.into_iter().filter(|&attr| !(exclude_press && attr == PlayerAttr::Pressurization));
+    Boosts::roll(rng, base, threshold, attrs)
 }
 
 struct InningEventPlugin;
-impl Plugin for InningEventPlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
-        let activated = |event: &str| game.events.has(String::from(event), 1);
+impl<R: Roll> Plugin<R> for InningEventPlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
+        let activated = |event: &str| game.events.has(event, 1);
         //note: inning events happen after the inning switch
         //they also happen after batter up apparently (?)
-        if !activated("TripleThreatDeactivation") && game.inning == 4 && game.scoreboard.top {
+        //the event log gets checked last since that walks back through the whole game
+        if game.inning == 4 && game.scoreboard.top && !activated("TripleThreatDeactivation") {
             let home_pitcher_deactivated = world.player(game.scoreboard.home_team.pitcher).mods.has(Mod::TripleThreat) && rng.next() < 0.333;
             let away_pitcher_deactivated = world.player(game.scoreboard.away_team.pitcher).mods.has(Mod::TripleThreat) && rng.next() < 0.333;
             if home_pitcher_deactivated || away_pitcher_deactivated {
@@ -868,15 +845,17 @@ impl Plugin for InningEventPlugin {
             }
         }
         if let Weather::Salmon = game.weather {
-            let away_team_scored = game.linescore_away.last().unwrap().abs() > 0.01;
-            let home_team_scored = if !game.scoreboard.top { false } else { game.linescore_home.last().unwrap().abs() > 0.01 };
+            //the half inning that just finished, and the whole inning if that was the bottom
+            let inning = if game.scoreboard.top { game.inning - 1 } else { game.inning };
+            let away_team_scored = game.linescore.runs_in(inning, true).abs() > 0.01;
+            let home_team_scored = game.scoreboard.top && game.linescore.runs_in(inning, false).abs() > 0.01;
             if game.events.len() > 0 && game.events.last() == "InningSwitch" && (away_team_scored || home_team_scored) {
-                let salmon_activated = rng.next() < 0.1375;
+                let salmon_activated = rng.next() < world.ruleset.constants.salmon;
                 if salmon_activated {
-                    let runs_lost = rng.next() < 0.675; //rough estimate
+                    let runs_lost = rng.next() < world.ruleset.constants.salmon_runs_lost;
                     if runs_lost {
                         if away_team_scored && home_team_scored {
-                            let double_runs_lost = rng.next() < 0.2; //VERY rough estimate
+                            let double_runs_lost = rng.next() < world.ruleset.constants.salmon_both_lost;
                             if double_runs_lost {
                                 return Some(Event::Salmon { away_runs_lost: true, home_runs_lost: true });
                             }
@@ -898,8 +877,8 @@ impl Plugin for InningEventPlugin {
 }
 
 struct ModPlugin;
-impl Plugin for ModPlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for ModPlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
         //this whole function? rulesets
         let batter = game.batter().unwrap();
         let batter_mods = &world.player(batter).mods;
@@ -907,15 +886,15 @@ impl Plugin for ModPlugin {
         let pitcher = game.pitcher();
         let pitcher_mods = &world.player(pitcher).mods;
         let pitcher_team_mods = &world.team(game.scoreboard.pitching_team().id).mods;
-        if batter_team_mods.has(Mod::Electric) && game.strikes > 0 && rng.next() < 0.2 {
+        if batter_team_mods.has(Mod::Electric) && game.strikes > 0 && rng.next() < world.ruleset.constants.zap {
             return Some(Event::Zap { batter: true });
-        } else if pitcher_team_mods.has(Mod::Electric) && game.balls > 0 && rng.next() < 0.2 {
+        } else if pitcher_team_mods.has(Mod::Electric) && game.balls > 0 && rng.next() < world.ruleset.constants.zap {
             return Some(Event::Zap { batter: false });
-        } else if pitcher_mods.has(Mod::DebtU) && !batter_mods.has(Mod::Unstable) && rng.next() < 0.02 { //estimate
+        } else if pitcher_mods.has(Mod::DebtU) && !batter_mods.has(Mod::Unstable) && rng.next() < world.ruleset.constants.debt_hbp {
             return Some(Event::HitByPitch { target: batter, hbp_type: 0 });
-        } else if pitcher_mods.has(Mod::RefinancedDebt) && !batter_mods.has(Mod::Flickering) && rng.next() < 0.02 { //estimate
+        } else if pitcher_mods.has(Mod::RefinancedDebt) && !batter_mods.has(Mod::Flickering) && rng.next() < world.ruleset.constants.debt_hbp {
             return Some(Event::HitByPitch { target: batter, hbp_type: 1 });
-        } else if pitcher_mods.has(Mod::ConsolidatedDebt) && !batter_mods.has(Mod::Repeating) && rng.next() < 0.02 { //estimate
+        } else if pitcher_mods.has(Mod::ConsolidatedDebt) && !batter_mods.has(Mod::Repeating) && rng.next() < world.ruleset.constants.debt_hbp {
             return Some(Event::HitByPitch { target: batter, hbp_type: 2 });
         } else if pitcher_mods.has(Mod::FriendOfCrows) {
             if let Weather::Birds = game.weather {
@@ -924,7 +903,7 @@ impl Plugin for ModPlugin {
                 }
             }
         }
-        if rng.next() < 0.005 && pitcher_mods.has(Mod::Mild) {
+        if rng.next() < world.ruleset.constants.mild_pitch && pitcher_mods.has(Mod::Mild) {
             if game.balls == 3 {
                 return Some(Event::MildWalk);
             } else {
@@ -932,11 +911,7 @@ impl Plugin for ModPlugin {
             }
         } else if game.balls == 0 && game.strikes == 0 {
             let myst = 0.0;
-        let charm_threshold = if world.season_ruleset == 18 {
-                0.014 + 0.006 * myst
-            } else {
-                0.015 + 0.02 * myst
-            };
+            let charm_threshold = world.ruleset.charm_threshold(myst);
             if batter_mods.has(Mod::Charm) && rng.next() < charm_threshold {
                 return Some(Event::CharmWalk);
             } else if pitcher_mods.has(Mod::Charm) && rng.next() < charm_threshold {
@@ -953,10 +928,10 @@ impl Plugin for ModPlugin {
 }
 
 struct PregamePlugin;
-impl Plugin for PregamePlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for PregamePlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
         if !game.started {
-            let activated = |event: &str| game.events.has(String::from(event), -1);
+            let activated = |event: &str| game.events.has(event, -1);
             if let Weather::Coffee3 = game.weather {
                 if !activated("TripleThreat") {
                     return Some(Event::TripleThreat);
@@ -966,23 +941,15 @@ impl Plugin for PregamePlugin {
             let mut underperforming = vec![];
             //todo: make this a separate event
             let superyummy = poll_for_mod(game, world, Mod::Superyummy, "current");
-            if superyummy.len() > 0 {
-                if let Weather::Peanuts = game.weather {
-                    overperforming = [overperforming, superyummy].concat();
-                } else {
-                    underperforming = [underperforming, superyummy].concat();
-                }
+            if let Weather::Peanuts = game.weather {
+                overperforming.extend(superyummy);
+            } else {
+                underperforming.extend(superyummy);
             }
             
             let perk = poll_for_mod(game, world, Mod::Perk, "current");
-            if perk.len() > 0 {
-                if let Weather::Coffee = game.weather {
-                    overperforming = [overperforming, perk].concat();
-                } else if let Weather::Coffee2 = game.weather {
-                    overperforming = [overperforming, perk].concat();
-                } else if let Weather::Coffee3 = game.weather {
-                    overperforming = [overperforming, perk].concat();
-                }
+            if let Weather::Coffee | Weather::Coffee2 | Weather::Coffee3 = game.weather {
+                overperforming.extend(perk);
             }
 
             //other performing code here
@@ -998,10 +965,10 @@ impl Plugin for PregamePlugin {
 }
 
 struct PartyPlugin;
-impl Plugin for PartyPlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for PartyPlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
         let party_roll = rng.next();
-        let party_threshold = if world.season_ruleset < 20 { 0.0055 } else { 0.00525 };
+        let party_threshold = world.ruleset.constants.party;
         if party_roll < party_threshold {
             let party_team = if rng.next() < 0.5 { world.team(game.scoreboard.home_team.id) } else { world.team(game.scoreboard.away_team.id) };
             if party_team.partying {
@@ -1026,22 +993,16 @@ impl Plugin for PartyPlugin {
 }
 
 struct FloodingPlugin;
-impl Plugin for FloodingPlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
+impl<R: Roll> Plugin<R> for FloodingPlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
         if let Weather::Flooding = game.weather {
             let fort = 0.0;
-            let flooding_threshold = match world.season_ruleset {
-                11..14 => 0.019 - 0.02 * fort,
-                14..17 => 0.013 - 0.012 * fort,
-                17 => 0.015 - 0.012 * fort,
-                18..24 => 0.016 - 0.012 * fort,
-                _ => 0.0,
-            };
+            let flooding_threshold = world.ruleset.flooding_threshold(fort);
             if rng.next() < flooding_threshold {
                 let mut elsewhere: Vec<Uuid> = Vec::new();
                 for runner in game.runners.iter() {
                     //todo: flooding threshold depends on myst and fort
-                    if rng.next() < 0.1 {
+                    if rng.next() < world.ruleset.constants.flooding_swept {
                         elsewhere.push(runner.id);
                     }
                 }
@@ -1056,17 +1017,17 @@ impl Plugin for FloodingPlugin {
 }
 
 struct ElsewherePlugin;
-impl Plugin for ElsewherePlugin {
-    fn tick(&self, game: &Game, world: &World, rng: &mut Rng) -> Option<Event> {
-        let elsewhere_return_threshold = match world.season_ruleset {
-            11 => 0.001,
-            12 => 0.000575,
-            13..18 => 0.0004,
-            18..24 => 0.00035,
-            _ => 0.0
-        };
-        let lineup = &world.team(game.scoreboard.batting_team().id).lineup;
-        let rotation = &world.team(game.scoreboard.batting_team().id).rotation;
+impl<R: Roll> Plugin<R> for ElsewherePlugin {
+    fn tick(&self, game: &Game, world: &World, rng: &mut R) -> Option<Event> {
+        let batting_team = world.team(game.scoreboard.batting_team().id);
+        let lineup = &batting_team.lineup;
+        let rotation = &batting_team.rotation;
+        //usually nobody's gone, and then there's nothing to roll for
+        let away = |&player: &Uuid| world.player(player).mods.has_any(Mod::Elsewhere.bit() | Mod::Scattered.bit());
+        if !lineup.iter().chain(rotation.iter()).any(away) {
+            return None;
+        }
+        let elsewhere_return_threshold = world.ruleset.elsewhere_return_threshold();
         let mut returned = Vec::new(); //ugh
         let mut letters = Vec::new();
         for &player in lineup {
@@ -1111,15 +1072,7 @@ impl Plugin for ElsewherePlugin {
         if returned.len() > 0 && game.events.last() != "ElsewhereReturn" {
             Some(Event::ElsewhereReturn { returned, letters })
         } else {
-            let unscatter_threshold = match world.season_ruleset {
-                11 | 12 => 0.00061,
-                13 => 0.0005,
-                14..17 => 0.0004,
-                17..20 => 0.00042,
-                20 | 21 => 0.000485,
-                22 | 23 => 0.000495,
-                _ => 0.0
-            };
+            let unscatter_threshold = world.ruleset.unscatter_threshold();
             let mut unscattered = Vec::new();
             for &player in lineup {
                 if world.player(player).mods.has(Mod::Scattered) && rng.next() < unscatter_threshold {
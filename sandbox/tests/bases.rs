@@ -0,0 +1,391 @@
+use sandbox::bases::{Advancement, Baserunners, MAX_BASES};
+use uuid::Uuid;
+
+//every base count from the usual four up to the most Baserunners allows
+const BASE_NUMBERS: std::ops::RangeInclusive<u8> = 4..=MAX_BASES;
+
+const BATTER: Uuid = Uuid::from_u128(0xba77e2);
+const PITCHER: Uuid = Uuid::from_u128(0x917c4e2);
+
+fn runner_id(base: u8) -> Uuid {
+    Uuid::from_u128(base as u128 + 1)
+}
+
+//runners on the bases in the mask, lead runner first like they usually sit in a game
+fn loaded(base_number: u8, occupied: u16) -> Baserunners {
+    let mut runners = Baserunners::new(base_number);
+    for base in (0..base_number - 1).rev() {
+        if occupied & (1 << base) != 0 {
+            runners.add(base, runner_id(base), PITCHER);
+        }
+    }
+    runners
+}
+
+fn occupancies(base_number: u8) -> std::ops::Range<u16> {
+    0..(1 << (base_number - 1))
+}
+
+fn positions(runners: &Baserunners) -> Vec<(Uuid, u8)> {
+    let mut positions: Vec<(Uuid, u8)> = runners.iter().map(|r| (r.id, r.base)).collect();
+    positions.sort_by_key(|&(_, base)| base);
+    positions
+}
+
+fn bases(runners: &Baserunners) -> Vec<u8> {
+    positions(runners).into_iter().map(|(_, base)| base).collect()
+}
+
+fn scored(advancement: &Advancement) -> Vec<Uuid> {
+    let mut scored: Vec<Uuid> = advancement.iter().map(|r| r.id).collect();
+    scored.sort();
+    scored
+}
+
+//nobody on home or past it, nobody sharing a base, and nobody lost or counted twice
+fn check_consistent(before: &Baserunners, after: &Baserunners, advancement: &Advancement, batter: Option<Uuid>) {
+    let home = after.home();
+    assert!(after.iter().all(|r| r.base < home), "runner left on or past home: {:?}", after);
+    let on_base = bases(after);
+    let mut distinct = on_base.clone();
+    distinct.dedup();
+    assert_eq!(on_base, distinct, "two runners on one base: {:?}", after);
+
+    let mut everyone: Vec<Uuid> = before.iter().map(|r| r.id).chain(batter).collect();
+    everyone.sort();
+    let mut accounted: Vec<Uuid> = after.iter().map(|r| r.id).chain(advancement.iter().map(|r| r.id)).collect();
+    accounted.sort();
+    assert_eq!(everyone, accounted);
+    assert_eq!(advancement.runs(), advancement.iter().count());
+    assert_eq!(advancement.any(), advancement.runs() > 0);
+}
+
+//the runner's base before the play, or None for the batter
+fn base_before(before: &Baserunners, id: Uuid) -> Option<u8> {
+    before.iter().find(|r| r.id == id).map(|r| r.base)
+}
+
+#[test]
+fn force_pushes_only_the_runners_in_the_way() {
+    for base_number in BASE_NUMBERS {
+        let home = base_number - 1;
+        for target in 0..home {
+            for occupied in occupancies(base_number) {
+                let before = loaded(base_number, occupied);
+                let mut after = before.clone();
+                let advancement = after.force(target, BATTER, PITCHER);
+                check_consistent(&before, &after, &advancement, Some(BATTER));
+                assert_eq!(after.at(target), Some(BATTER));
+
+                //walk up from the batter: each runner either stays put, or is pushed to the
+                //base right past whoever's behind them because that was the first free one
+                let mut free = target + 1;
+                let mut expected_runs = 0;
+                for base in 0..home {
+                    if occupied & (1 << base) == 0 {
+                        continue;
+                    }
+                    let landing = base.max(free);
+                    if landing >= home {
+                        expected_runs += 1;
+                        assert!(advancement.iter().any(|r| r.id == runner_id(base)));
+                    } else {
+                        assert_eq!(after.at(landing), Some(runner_id(base)), "{} bases, force to {}, occupied {:b}", base_number, target, occupied);
+                    }
+                    free = landing + 1;
+                }
+                assert_eq!(advancement.runs(), expected_runs);
+            }
+        }
+    }
+}
+
+#[test]
+fn walks() {
+    //bases loaded walks a run in
+    let mut runners = loaded(4, 0b111);
+    let advancement = runners.force(0, BATTER, PITCHER);
+    assert_eq!(scored(&advancement), vec![runner_id(2)]);
+    assert_eq!(bases(&runners), vec![0, 1, 2]);
+
+    //a gap stops the force
+    let mut runners = loaded(4, 0b101);
+    let advancement = runners.force(0, BATTER, PITCHER);
+    assert!(!advancement.any());
+    assert_eq!(positions(&runners), vec![(BATTER, 0), (runner_id(0), 1), (runner_id(2), 2)]);
+
+    //with a fifth base, a runner on third is only forced to fourth
+    let mut runners = loaded(5, 0b0111);
+    let advancement = runners.force(0, BATTER, PITCHER);
+    assert!(!advancement.any());
+    assert_eq!(bases(&runners), vec![0, 1, 2, 3]);
+
+    //nobody on first, nobody moves
+    let mut runners = loaded(5, 0b1110);
+    let advancement = runners.force(0, BATTER, PITCHER);
+    assert!(!advancement.any());
+    assert_eq!(bases(&runners), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn instincts() {
+    //walking to third clears first and second, but a runner on fourth only gets pushed to home if forced
+    let mut runners = loaded(5, 0b1010);
+    let advancement = runners.force(2, BATTER, PITCHER);
+    assert_eq!(scored(&advancement), vec![runner_id(3)]);
+    assert_eq!(positions(&runners), vec![(BATTER, 2), (runner_id(1), 3)]);
+
+    //the old model moved everyone three bases, which scored this runner from fourth
+    let mut runners = loaded(5, 0b1000);
+    let advancement = runners.force(2, BATTER, PITCHER);
+    assert!(!advancement.any());
+    assert_eq!(positions(&runners), vec![(BATTER, 2), (runner_id(3), 3)]);
+
+    //walking to second with a runner on first pushes them to third
+    let mut runners = loaded(4, 0b001);
+    let advancement = runners.force(1, BATTER, PITCHER);
+    assert!(!advancement.any());
+    assert_eq!(positions(&runners), vec![(BATTER, 1), (runner_id(0), 2)]);
+
+    //and with the bases loaded, the runners on second and third both come in
+    let mut runners = loaded(4, 0b111);
+    let advancement = runners.force(1, BATTER, PITCHER);
+    assert_eq!(scored(&advancement), vec![runner_id(1), runner_id(2)]);
+    assert_eq!(positions(&runners), vec![(BATTER, 1), (runner_id(0), 2)]);
+}
+
+#[test]
+fn advance_marked_every_mask() {
+    for base_number in BASE_NUMBERS {
+        for occupied in occupancies(base_number) {
+            let before = loaded(base_number, occupied);
+            for amount in 0..=4 {
+                for mask in 0..(1u16 << before.len()) {
+                    let mask = mask as u8;
+                    let mut after = before.clone();
+                    let advancement = after.advance_marked(amount, mask);
+                    check_consistent(&before, &after, &advancement, None);
+
+                    //everyone moves `amount`, and only the marked runners ever get one more
+                    for (i, runner) in before.iter().enumerate() {
+                        let extra = mask & (1 << i) != 0;
+                        match after.iter().find(|r| r.id == runner.id) {
+                            Some(r) if extra => assert!(r.base == runner.base + amount || r.base == runner.base + amount + 1),
+                            Some(r) => assert_eq!(r.base, runner.base + amount),
+                            None => assert!(runner.base + amount + extra as u8 >= after.home()),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn advance_marked() {
+    //bit 0 is the lead runner, since that's who comes first in Baserunners
+    //both marked: the lead runner scores, which opens up third for the runner behind
+    let mut runners = loaded(4, 0b011);
+    let advancement = runners.advance_marked(1, 0b11);
+    assert_eq!(scored(&advancement), vec![runner_id(1)]);
+    assert_eq!(positions(&runners), vec![(runner_id(0), 2)]);
+
+    //only the trailing runner marked: the lead runner stays on third and blocks them
+    let mut runners = loaded(4, 0b011);
+    let advancement = runners.advance_marked(1, 0b10);
+    assert!(!advancement.any());
+    assert_eq!(positions(&runners), vec![(runner_id(0), 1), (runner_id(1), 2)]);
+
+    //only the lead runner marked
+    let mut runners = loaded(4, 0b011);
+    let advancement = runners.advance_marked(1, 0b01);
+    assert_eq!(scored(&advancement), vec![runner_id(1)]);
+    assert_eq!(positions(&runners), vec![(runner_id(0), 1)]);
+
+    //a runner already home doesn't take an extra base, and the runner behind still gets theirs
+    let mut runners = loaded(4, 0b101);
+    let advancement = runners.advance_marked(1, 0b11);
+    assert_eq!(scored(&advancement), vec![runner_id(2)]);
+    assert_eq!(positions(&runners), vec![(runner_id(0), 2)]);
+
+    //nobody moves off a full set of bases without a push
+    let mut runners = loaded(4, 0b111);
+    let advancement = runners.advance_marked(0, 0b100);
+    assert!(!advancement.any());
+    assert_eq!(positions(&runners), vec![(runner_id(0), 0), (runner_id(1), 1), (runner_id(2), 2)]);
+
+    //with a fifth base, third to fourth and the extra base brings them home
+    let mut runners = loaded(5, 0b0100);
+    let advancement = runners.advance_marked(1, 0b1);
+    assert_eq!(scored(&advancement), vec![runner_id(2)]);
+    assert!(runners.empty());
+    let mut runners = loaded(5, 0b0100);
+    let advancement = runners.advance_marked(1, 0);
+    assert!(!advancement.any());
+    assert_eq!(positions(&runners), vec![(runner_id(2), 3)]);
+}
+
+#[test]
+#[should_panic]
+fn too_many_bases() {
+    Baserunners::new(MAX_BASES + 1);
+}
+
+#[test]
+fn advance_all_every_amount() {
+    for base_number in BASE_NUMBERS {
+        let home = base_number - 1;
+        for occupied in occupancies(base_number) {
+            let before = loaded(base_number, occupied);
+            for amount in 0..=base_number {
+                let mut after = before.clone();
+                let advancement = after.advance_all(amount);
+                check_consistent(&before, &after, &advancement, None);
+                for runner in before.iter() {
+                    if runner.base + amount >= home {
+                        assert!(advancement.iter().any(|r| r.id == runner.id));
+                    } else {
+                        assert_eq!(after.at(runner.base + amount), Some(runner.id));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn hits_every_mask() {
+    for base_number in BASE_NUMBERS {
+        let home = base_number - 1;
+        for occupied in occupancies(base_number) {
+            let before = loaded(base_number, occupied);
+            for bases in 1..=4 {
+                for mask in 0..(1u16 << before.len()) {
+                    let mask = mask as u8;
+                    let mut after = before.clone();
+                    let advancement = after.hit(bases, mask, BATTER, PITCHER);
+                    check_consistent(&before, &after, &advancement, Some(BATTER));
+
+                    //the runners move before the batter lands, so the batter never blocks anyone:
+                    //everyone who'd score without the extra base does, and nobody else but the marked ones
+                    let batter_scored = bases - 1 >= home;
+                    let surely = before.iter().filter(|r| r.base + bases >= home).count();
+                    let maybe = before.iter().enumerate().filter(|&(i, r)| r.base + bases + (mask & (1 << i) != 0) as u8 >= home).count();
+                    assert!((surely..=maybe).contains(&(advancement.runs() - batter_scored as usize)));
+                    if batter_scored {
+                        assert!(!after.contains(BATTER));
+                    } else {
+                        assert_eq!(after.at(bases - 1), Some(BATTER));
+                    }
+                    for scored in advancement.iter() {
+                        assert!(scored.id == BATTER || base_before(&before, scored.id).is_some());
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn hits() {
+    //a runner who scores doesn't block the one behind from taking an extra base
+    let mut runners = loaded(4, 0b011);
+    let advancement = runners.hit(2, 0b11, BATTER, PITCHER);
+    assert_eq!(scored(&advancement), vec![runner_id(0), runner_id(1)]);
+    assert_eq!(positions(&runners), vec![(BATTER, 1)]);
+
+    //but a runner who's still on base does
+    let mut runners = loaded(4, 0b011);
+    let advancement = runners.hit(1, 0b10, BATTER, PITCHER);
+    assert!(!advancement.any());
+    assert_eq!(positions(&runners), vec![(BATTER, 0), (runner_id(0), 1), (runner_id(1), 2)]);
+
+    //a quadruple without a fifth base brings the batter all the way home
+    let mut runners = loaded(4, 0b000);
+    let advancement = runners.hit(4, 0, BATTER, PITCHER);
+    assert_eq!(scored(&advancement), vec![BATTER]);
+    assert!(runners.empty());
+
+    //and with one, the batter stops on fourth
+    let mut runners = loaded(5, 0b0001);
+    let advancement = runners.hit(4, 0, BATTER, PITCHER);
+    assert_eq!(scored(&advancement), vec![runner_id(0)]);
+    assert_eq!(positions(&runners), vec![(BATTER, 3)]);
+}
+
+#[test]
+fn steals() {
+    for base_number in BASE_NUMBERS {
+        let home = base_number - 1;
+        for occupied in occupancies(base_number) {
+            let before = loaded(base_number, occupied);
+            for base in 0..home {
+                if !before.occupied(base) || !before.can_advance(base) {
+                    continue;
+                }
+                let mut after = before.clone();
+                let advancement = after.advance(base);
+                check_consistent(&before, &after, &advancement, None);
+                if base + 1 == home {
+                    //stealing home is a run
+                    assert_eq!(scored(&advancement), vec![runner_id(base)]);
+                } else {
+                    assert!(!advancement.any());
+                    assert_eq!(after.at(base + 1), Some(runner_id(base)));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn can_advance_only_blocks_on_an_occupied_base() {
+    for base_number in BASE_NUMBERS {
+        let home = base_number - 1;
+        for occupied in occupancies(base_number) {
+            let runners = loaded(base_number, occupied);
+            for base in 0..home {
+                let blocked = base + 1 < home && occupied & (1 << (base + 1)) != 0;
+                assert_eq!(runners.can_advance(base), !blocked);
+            }
+        }
+    }
+}
+
+#[test]
+fn outs_on_the_bases() {
+    for base_number in BASE_NUMBERS {
+        let home = base_number - 1;
+        for occupied in occupancies(base_number) {
+            if occupied & 1 == 0 {
+                continue;
+            }
+            let runners = loaded(base_number, occupied);
+            //the fielder's choice gets the lead runner of the ones forced from first
+            let forced = (0..home).take_while(|&base| occupied & (1 << base) != 0).count() as u8;
+            assert_eq!(runners.pick_runner_fc(), forced - 1);
+
+            //a double play takes the runner out and moves everyone else up one
+            let out = runners.pick_runner(0.0);
+            let mut after = runners.clone();
+            assert_eq!(after.remove(out), Some(runner_id(out)));
+            assert_eq!(after.remove(out), None);
+            let advancement = after.advance_all(1);
+            let expected_runs = (0..home).filter(|&base| base != out && occupied & (1 << base) != 0 && base + 1 >= home).count();
+            assert_eq!(advancement.runs(), expected_runs);
+        }
+    }
+}
+
+#[test]
+fn scoring_runners_keep_their_pitcher() {
+    let reliever = Uuid::from_u128(0x4e11e5e4);
+    let mut runners = loaded(4, 0b111);
+    let advancement = runners.force(0, BATTER, reliever);
+    assert_eq!(advancement.iter().map(|r| r.pitcher).collect::<Vec<Uuid>>(), vec![PITCHER]);
+
+    let advancement = runners.hit(4, 0, Uuid::from_u128(0xba77e3), reliever);
+    let mut charged: Vec<(Uuid, Uuid)> = advancement.iter().map(|r| (r.id, r.pitcher)).collect();
+    charged.sort();
+    assert_eq!(charged, vec![(runner_id(0), PITCHER), (runner_id(1), PITCHER), (BATTER, reliever), (Uuid::from_u128(0xba77e3), reliever)]);
+}
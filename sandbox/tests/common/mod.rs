@@ -0,0 +1,26 @@
+use sandbox::{entities::World, rng::Rng, ruleset::Ruleset};
+use uuid::Uuid;
+
+//two generated teams on the same seed every time, as (world, home, away)
+pub fn world() -> (World, Uuid, Uuid) {
+    let mut rng = Rng::new(69, 420);
+    let mut world = World::new(Ruleset::new(11).unwrap());
+    let away = world.gen_team(&mut rng, "Away".to_string(), "A".to_string());
+    let home = world.gen_team(&mut rng, "Home".to_string(), "H".to_string());
+    (world, home, away)
+}
+
+//the home team made into a copy of the away team, player for player
+#[allow(dead_code)]
+pub fn mirrored() -> (World, Uuid, Uuid) {
+    let (mut world, home, away) = world();
+    for (roster, copy) in [
+        (world.team(away).lineup.clone(), world.team(home).lineup.clone()),
+        (world.team(away).rotation.clone(), world.team(home).rotation.clone()),
+    ] {
+        for (&from, &to) in roster.iter().zip(copy.iter()) {
+            world.player_mut(to).stats = world.player(from).stats;
+        }
+    }
+    (world, home, away)
+}
@@ -0,0 +1,22 @@
+use sandbox::constants::{Constants, CONSTANTS_VERSION};
+
+#[test]
+fn files_round_trip() {
+    let mut changed = Constants::new(11);
+    changed.set("zap = 0.25").unwrap();
+    changed.set("party=0.006").unwrap();
+    let mut read = Constants::new(11);
+    read.read(&changed.write()).unwrap();
+    assert_eq!(read.values(), changed.values());
+    assert_eq!(read.zap, 0.25);
+    //every constant gets written out
+    assert_eq!(changed.write().lines().count(), changed.values().len() + 1);
+}
+
+#[test]
+fn other_versions_are_refused() {
+    let mut constants = Constants::new(11);
+    assert!(constants.read(&format!("version = {}\nzap = 0.3", CONSTANTS_VERSION)).is_ok());
+    assert!(constants.read(&format!("version = {}\nzap = 0.4", CONSTANTS_VERSION + 1)).is_err());
+    assert!(constants.set("not_a_constant = 1").is_err());
+}
@@ -0,0 +1,79 @@
+use sandbox::{
+    entities::{PlayerAttr, World},
+    explain::{Matchup, Role, Threshold},
+    rng::Rng,
+    Game, MultiplierData, Weather,
+};
+
+mod common;
+
+//the top of the first with the away team's leadoff hitter up. the seed rolls them next to no
+//divinity or musclitude, which would be clamped, so they get average ones
+fn setup() -> (World, Game) {
+    let (mut world, home, away) = common::world();
+    let batter = world.team(away).lineup[0];
+    world.player_mut(batter).set(PlayerAttr::Divinity, 0.5);
+    world.player_mut(batter).set(PlayerAttr::Musclitude, 0.5);
+    let mut game = Game::new(home, away, 0, Some(Weather::Sun), &world, &mut Rng::new(1, 2));
+    game.assign_batter(batter);
+    (world, game)
+}
+
+//how a matchup gets put together: the pitcher, the batter, and whoever's fielding and running
+fn matchup<'a>(world: &'a World, game: &Game, data: &'a MultiplierData<'a>) -> Matchup<'a> {
+    let batter = world.player(game.batter().unwrap());
+    Matchup {
+        pitcher: world.player(game.pitcher()),
+        batter,
+        fielder: world.player(world.team(game.scoreboard.pitching_team().id).lineup[0]),
+        runner: batter,
+        ruleset: &world.ruleset,
+        multiplier_data: data,
+    }
+}
+
+#[test]
+fn explanations_line_up_with_the_thresholds() {
+    let (world, game) = setup();
+    let data = game.compute_multiplier_data(&world);
+    let m = matchup(&world, &game, &data);
+    for threshold in Threshold::ALL {
+        let explanation = m.explain(threshold);
+        assert_eq!(explanation.value, m.threshold(threshold));
+        assert_eq!(explanation.stats.len(), explanation.sensitivities.len());
+        for (stat, sensitivity) in explanation.stats.iter().zip(explanation.sensitivities.iter()) {
+            assert_eq!((stat.role, stat.attr), (sensitivity.role, sensitivity.attr));
+            assert_eq!(stat.raw, m.player(stat.role).get(stat.attr));
+            //a small nudge moves the threshold about as much as the derivative says
+            let delta = m.delta(threshold, sensitivity.role, sensitivity.attr, 1e-4);
+            assert!((delta - 1e-4 * sensitivity.derivative).abs() < 1e-6, "{:?} {:?} {:?}", threshold, stat.role, stat.attr);
+        }
+    }
+    //and the home run threshold comes down to the batter and pitcher, never the runner's baserunning
+    let explanation = m.explain(Threshold::HomeRun);
+    assert!(explanation.sensitivities.iter().any(|s| s.role == Role::Batter && s.attr == PlayerAttr::Divinity && s.derivative > 0.0));
+    assert!(explanation.sensitivities.iter().all(|s| s.attr != PlayerAttr::BaseThirst));
+    assert_eq!(explanation.to_string().lines().count(), explanation.stats.len() + 1);
+}
+
+#[test]
+fn clamped_stats_are_reported() {
+    let (mut world, game) = setup();
+    //ruthless enough to pin the strike threshold at its cap
+    world.player_mut(game.pitcher()).set(PlayerAttr::Ruthlessness, 5.0);
+    let data = game.compute_multiplier_data(&world);
+    let m = matchup(&world, &game, &data);
+    let explanation = m.explain(Threshold::Strike);
+    let ruthlessness = explanation.sensitivities.iter().find(|s| s.role == Role::Pitcher && s.attr == PlayerAttr::Ruthlessness).unwrap();
+    assert!(ruthlessness.clamped);
+    assert_eq!(ruthlessness.derivative, 0.0);
+    assert!(explanation.to_string().contains("Pitcher Ruthlessness") && explanation.to_string().contains("clamped"));
+    //stats the strike threshold never looks at still don't show up
+    assert!(explanation.sensitivities.iter().all(|s| s.attr != PlayerAttr::Laserlikeness));
+
+    //and nothing's clamped when the pitcher is back to normal
+    world.player_mut(game.pitcher()).set(PlayerAttr::Ruthlessness, 0.5);
+    let data = game.compute_multiplier_data(&world);
+    let explanation = matchup(&world, &game, &data).explain(Threshold::Strike);
+    assert!(explanation.sensitivities.iter().all(|s| !s.clamped));
+}
@@ -0,0 +1,375 @@
+use sandbox::{
+    entities::World,
+    ruleset::Ruleset,
+    events::Event,
+    game_end::{self, Leader},
+    mods::{Mod, ModLifetime},
+    rng::Rng,
+    sim::Sim,
+    Game, Weather,
+};
+use uuid::Uuid;
+
+mod common;
+
+fn game(world: &World, home: Uuid, away: Uuid, weather: Weather) -> Game {
+    Game::new(home, away, 0, Some(weather), world, &mut Rng::new(1, 2))
+}
+
+//none of the eras we have walk off, so the walk-off rules get tested on a ruleset that does
+fn with_walk_offs(world: &World) -> Ruleset {
+    let mut ruleset = world.ruleset.clone();
+    ruleset.walk_offs = true;
+    ruleset
+}
+
+//a game at some point: the half inning, how many outs, and the score
+fn at(game: &mut Game, inning: i16, top: bool, outs: i16, away: f64, home: f64) {
+    game.inning = inning;
+    game.scoreboard.top = top;
+    game.outs = outs;
+    game.scoreboard.away_team.score = away;
+    game.scoreboard.home_team.score = home;
+}
+
+#[test]
+fn ties_have_slack() {
+    assert_eq!(game_end::leader(1.0, 1.0), Leader::Tied);
+    assert_eq!(game_end::leader(0.1 + 0.2, 0.3), Leader::Tied);
+    assert_eq!(game_end::leader(1.0, 1.005), Leader::Tied);
+    assert_eq!(game_end::leader(0.0, -0.0), Leader::Tied);
+    assert_eq!(game_end::leader(1.1, 1.0), Leader::Away);
+    assert_eq!(game_end::leader(1.0, 1.1), Leader::Home);
+}
+
+#[test]
+fn negative_scores() {
+    assert_eq!(game_end::leader(-0.5, -1.0), Leader::Away);
+    assert_eq!(game_end::leader(-1.0, -0.5), Leader::Home);
+    assert_eq!(game_end::leader(-1.0, 0.0), Leader::Home);
+
+    let (world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::PolarityMinus);
+    //the home team scoring their way further below zero in the bottom of the ninth doesn't end anything
+    at(&mut game, 9, false, 1, -1.0, -1.5);
+    assert!(!game_end::is_over(&game, &world.ruleset));
+    //until the inning's over
+    at(&mut game, 9, false, 3, -1.0, -1.5);
+    assert!(game_end::is_over(&game, &world.ruleset));
+    assert_eq!(game_end::result(&game).0, game.scoreboard.away_team.id);
+    //but climbing past the away team is a walk-off, where there are walk-offs
+    at(&mut game, 9, false, 1, -1.0, -0.5);
+    assert!(!game_end::is_over(&game, &world.ruleset));
+    assert!(game_end::is_over(&game, &with_walk_offs(&world)));
+    assert_eq!(game_end::result(&game).0, game.scoreboard.home_team.id);
+}
+
+#[test]
+fn nothing_ends_before_the_last_inning() {
+    let (world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::Sun);
+    for inning in 1..9 {
+        for top in [true, false] {
+            for outs in 0..=3 {
+                for (away, home) in [(0.0, 5.0), (5.0, 0.0), (2.0, 2.0)] {
+                    at(&mut game, inning, top, outs, away, home);
+                    assert!(!game_end::is_over(&game, &world.ruleset), "inning {} top {} outs {}", inning, top, outs);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn end_of_the_top_of_the_ninth() {
+    let (world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::Sun);
+    //the home team doesn't need to bat
+    at(&mut game, 9, true, 3, 1.0, 2.0);
+    assert!(game_end::is_over(&game, &world.ruleset));
+    //they do if they're behind or tied
+    at(&mut game, 9, true, 3, 2.0, 1.0);
+    assert!(!game_end::is_over(&game, &world.ruleset));
+    at(&mut game, 9, true, 3, 2.0, 2.0);
+    assert!(!game_end::is_over(&game, &world.ruleset));
+    //and nothing ends mid-inning in the top
+    at(&mut game, 9, true, 2, 1.0, 2.0);
+    assert!(!game_end::is_over(&game, &world.ruleset));
+}
+
+#[test]
+fn end_of_the_bottom_of_the_ninth() {
+    let (world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::Sun);
+    at(&mut game, 9, false, 3, 2.0, 1.0);
+    assert!(game_end::is_over(&game, &world.ruleset));
+    assert_eq!(game_end::result(&game), (game.scoreboard.away_team.id, game.scoreboard.home_team.id));
+    //tied goes to extras
+    at(&mut game, 9, false, 3, 2.0, 2.0);
+    assert!(!game_end::is_over(&game, &world.ruleset));
+}
+
+#[test]
+fn walk_offs() {
+    let (world, home, away) = common::world();
+    let ruleset = with_walk_offs(&world);
+    let mut game = game(&world, home, away, Weather::Sun);
+    //the home team taking the lead in the bottom of the ninth ends it right there, whatever the outs
+    for outs in 0..=2 {
+        at(&mut game, 9, false, outs, 3.0, 4.0);
+        assert!(game_end::walk_off(&game, &ruleset));
+        assert!(game_end::is_over(&game, &ruleset));
+    }
+    //tying it doesn't
+    at(&mut game, 9, false, 1, 3.0, 3.0);
+    assert!(!game_end::is_over(&game, &ruleset));
+    //not before the ninth
+    at(&mut game, 8, false, 1, 3.0, 4.0);
+    assert!(!game_end::is_over(&game, &ruleset));
+    //and it works in extras too
+    at(&mut game, 13, false, 0, 3.0, 3.2);
+    assert!(game_end::is_over(&game, &ruleset));
+}
+
+#[test]
+fn supported_eras_play_the_half_out() {
+    let (world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::Sun);
+    for ruleset in (11..=14).filter_map(|season| Ruleset::new(season).ok()) {
+        let season = ruleset.season;
+        assert!(!ruleset.walk_offs, "season {}", season);
+        //the home team going ahead in the bottom of the ninth or an extra inning keeps batting until the third out
+        for (inning, outs) in [(9, 0), (9, 1), (9, 2), (12, 2)] {
+            at(&mut game, inning, false, outs, 3.0, 4.0);
+            assert!(!game_end::walk_off(&game, &ruleset));
+            assert!(!game_end::is_over(&game, &ruleset), "season {} inning {} outs {}", season, inning, outs);
+        }
+        at(&mut game, 9, false, 3, 3.0, 4.0);
+        assert!(game_end::is_over(&game, &ruleset));
+        assert_eq!(game_end::result(&game).0, home);
+    }
+}
+
+#[test]
+fn extra_innings() {
+    let (world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::Sun);
+    //ties keep going for as long as they're tied
+    for inning in 10..30 {
+        at(&mut game, inning, false, 3, 4.0, 4.0);
+        assert!(!game_end::is_over(&game, &world.ruleset));
+        at(&mut game, inning, true, 3, 4.0, 4.0);
+        assert!(!game_end::is_over(&game, &world.ruleset));
+    }
+    //the away team going ahead in the top of an extra inning still gives the home team a turn
+    at(&mut game, 10, true, 3, 5.0, 4.0);
+    assert!(!game_end::is_over(&game, &world.ruleset));
+    at(&mut game, 10, false, 3, 5.0, 4.0);
+    assert!(game_end::is_over(&game, &world.ruleset));
+    //and the home team going ahead in the top (a black hole, say) means they don't need it
+    at(&mut game, 10, true, 3, -5.0, 4.0);
+    assert!(game_end::is_over(&game, &world.ruleset));
+}
+
+#[test]
+fn sun_2_and_black_holes() {
+    let (world, home, away) = common::world();
+    let ruleset = with_walk_offs(&world);
+    for weather in [Weather::Sun2, Weather::BlackHole] {
+        let mut game = game(&world, home, away, weather);
+        //the home team just got to 10, which is about to go away again. no walk-off until it has
+        at(&mut game, 9, false, 1, 5.0, 10.5);
+        assert!(game_end::weather_pending(&game));
+        assert!(!game_end::is_over(&game, &ruleset));
+        at(&mut game, 9, false, 1, 5.0, 0.5);
+        assert!(!game_end::weather_pending(&game));
+        assert!(!game_end::is_over(&game, &ruleset));
+
+        //at the end of a half, the scores count as what they'll settle to
+        at(&mut game, 9, true, 3, 12.0, 4.0);
+        assert_eq!(game_end::settled_scores(&game), (2.0, 4.0));
+        assert!(game_end::is_over(&game, &world.ruleset));
+        assert_eq!(game_end::result(&game).0, game.scoreboard.home_team.id);
+        at(&mut game, 9, false, 3, 8.0, 15.0);
+        assert_eq!(game_end::settled_scores(&game), (8.0, 5.0));
+        assert_eq!(game_end::result(&game).0, game.scoreboard.away_team.id);
+    }
+    //any other weather leaves 10 runs alone
+    let mut game = game(&world, home, away, Weather::Sun);
+    at(&mut game, 9, false, 1, 5.0, 10.5);
+    assert!(!game_end::weather_pending(&game));
+    assert!(game_end::is_over(&game, &ruleset));
+}
+
+#[test]
+fn salmon_rewinds_into_the_last_inning() {
+    let (mut world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::Salmon);
+    //the away team scored 2 in the top of the ninth, the home team tied it up in the bottom
+    at(&mut game, 9, true, 0, 0.0, 0.0);
+    game.linescore.start(9, true);
+    game.linescore.add_runs(9, true, 2.0);
+    game.linescore.start(9, false);
+    game.linescore.add_runs(9, false, 2.0);
+    at(&mut game, 10, true, 0, 2.0, 2.0);
+    game.linescore.start(10, true);
+    //then the salmon take the away team's runs and the ninth starts over
+    Event::Salmon { home_runs_lost: false, away_runs_lost: true }.apply(&mut game, &mut world);
+    assert_eq!((game.inning, game.scoreboard.top), (9, true));
+    assert_eq!(game.scoreboard.away_team.score, 0.0);
+    assert_eq!(game.linescore.runs_in(9, true), 0.0);
+    assert!(!game_end::is_over(&game, &world.ruleset));
+    //the home team's runs stayed, so now the top of the ninth ending is the end of the game
+    game.outs = 3;
+    assert!(game_end::is_over(&game, &world.ruleset));
+    assert_eq!(game_end::result(&game).0, game.scoreboard.home_team.id);
+}
+
+#[test]
+fn shorter_games_by_decree() {
+    let (world, home, away) = common::world();
+    let mut ruleset = with_walk_offs(&world);
+    ruleset.innings = 7;
+    let mut game = game(&world, home, away, Weather::Sun);
+    at(&mut game, 7, false, 3, 2.0, 1.0);
+    assert!(game_end::is_over(&game, &ruleset));
+    assert!(!game_end::is_over(&game, &world.ruleset));
+    at(&mut game, 7, false, 1, 1.0, 2.0);
+    assert!(game_end::walk_off(&game, &ruleset));
+    at(&mut game, 6, false, 3, 2.0, 1.0);
+    assert!(!game_end::is_over(&game, &ruleset));
+}
+
+#[test]
+fn played_games_end_by_the_rules() {
+    for weather in [Weather::Sun, Weather::Sun2, Weather::BlackHole, Weather::Salmon, Weather::PolarityPlus] {
+        for seed in 1..=40 {
+            let (mut world, home, away) = common::world();
+            let mut rng = Rng::new(seed, 420);
+            let mut game = Game::new(home, away, 0, Some(weather.clone()), &world, &mut rng);
+            let mut sim = Sim::new(&mut world, &mut rng);
+            let over = loop {
+                let evt = sim.next(&game);
+                evt.apply(&mut game, sim.world);
+                if let Event::GameOver = evt {
+                    break game.clone();
+                }
+            };
+            assert!(over.inning >= 9);
+            let (away, home) = game_end::settled_scores(&over);
+            assert_ne!(game_end::leader(away, home), Leader::Tied);
+            //without walk-offs every game ends on a third out
+            assert!(over.outs >= 3);
+            let (winner, _) = game_end::result(&over);
+            assert_eq!(winner == over.scoreboard.home_team.id, game_end::leader(away, home) == Leader::Home);
+        }
+    }
+}
+
+#[test]
+fn shame() {
+    let (world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::Sun);
+    //the home team not needing the bottom of the ninth shames the away team
+    at(&mut game, 9, true, 3, 1.0, 3.5);
+    assert!(game_end::shamed(&game, &world.ruleset));
+    assert_eq!(game_end::shame_runs(&game), 2.5);
+    //same in extras
+    at(&mut game, 11, true, 3, 1.0, 3.5);
+    assert!(game_end::shamed(&game, &world.ruleset));
+    //a game that ends in the bottom isn't a shame, walk-off or not
+    at(&mut game, 9, false, 3, 3.5, 1.0);
+    assert!(!game_end::shamed(&game, &world.ruleset));
+    at(&mut game, 9, false, 1, 1.0, 3.5);
+    assert!(!game_end::shamed(&game, &world.ruleset));
+    //the home team leading after the top of any earlier inning isn't either
+    at(&mut game, 8, true, 3, 1.0, 3.5);
+    assert!(!game_end::shamed(&game, &world.ruleset));
+    //and a ruleset without shame never shames anyone
+    let mut ruleset = world.ruleset.clone();
+    ruleset.shame = false;
+    at(&mut game, 9, true, 3, 1.0, 3.5);
+    assert!(!game_end::shamed(&game, &ruleset));
+}
+
+#[test]
+fn targeted_shame_carries_into_the_next_game() {
+    let (mut world, home, away) = common::world();
+    world.team_mut(away).mods.add(Mod::TargetedShame, ModLifetime::Permanent);
+
+    let mut game = game(&world, home, away, Weather::Sun);
+    at(&mut game, 9, true, 3, 1.0, 3.5);
+    Event::GameOver.apply(&mut game, &mut world);
+    assert!(game.shame);
+    assert_eq!(world.team(away).shames, 1);
+    assert_eq!(world.team(home).shamings, 1);
+    assert_eq!(world.team(away).shame_runs, 2.5);
+
+    //the next game starts in the hole, whether they're home or away
+    let next = Game::new(away, home, 1, Some(Weather::Sun), &world, &mut Rng::new(3, 4));
+    assert_eq!(next.scoreboard.home_team.score, -2.5);
+    assert_eq!(next.scoreboard.away_team.score, 0.0);
+
+    //and only that one
+    let mut next = next;
+    at(&mut next, 9, false, 3, 2.0, 1.0);
+    Event::GameOver.apply(&mut next, &mut world);
+    assert!(!next.shame);
+    assert_eq!(world.team(away).shame_runs, 0.0);
+    let after = Game::new(away, home, 2, Some(Weather::Sun), &world, &mut Rng::new(3, 4));
+    assert_eq!(after.scoreboard.home_team.score, 0.0);
+}
+
+#[test]
+fn shame_without_targeted_shame_carries_nothing() {
+    let (mut world, home, away) = common::world();
+    let mut game = game(&world, home, away, Weather::Sun);
+    at(&mut game, 9, true, 3, 1.0, 3.5);
+    Event::GameOver.apply(&mut game, &mut world);
+    assert!(game.shame);
+    assert_eq!(world.team(away).shames, 1);
+    assert_eq!(world.team(away).shame_runs, 0.0);
+    let next = Game::new(home, away, 1, Some(Weather::Sun), &world, &mut Rng::new(3, 4));
+    assert_eq!(next.scoreboard.away_team.score, 0.0);
+}
+
+#[test]
+fn scheduled_games_pick_up_shame_on_the_day() {
+    let (mut world, home, away) = common::world();
+    world.team_mut(away).mods.add(Mod::TargetedShame, ModLifetime::Permanent);
+    let mut rng = Rng::new(5, 6);
+    //made up front like a season schedule, before anyone's been shamed
+    let schedule = vec![
+        Game::new(home, away, 0, Some(Weather::Sun), &world, &mut rng),
+        Game::new(away, home, 1, Some(Weather::Sun), &world, &mut rng),
+    ];
+    let mut played = Vec::new();
+    let mut shamed_by = 0.0;
+    for (day, mut game) in schedule.into_iter().enumerate() {
+        game.prepare(&world);
+        if day == 0 {
+            //a head start the away team won't come back from, so they get shamed
+            game.scoreboard.home_team.score = 100.0;
+        }
+        played.push(game.scoreboard.clone());
+        let mut sim = Sim::new(&mut world, &mut rng);
+        loop {
+            let evt = sim.next(&game);
+            evt.apply(&mut game, sim.world);
+            if let Event::GameOver = evt {
+                break;
+            }
+        }
+        if day == 0 {
+            assert!(game.shame);
+            shamed_by = game_end::shame_runs(&game);
+            assert!(shamed_by > 0.0);
+            assert_eq!(world.team(away).shame_runs, shamed_by);
+        }
+    }
+    //the shamed team was home the next day, and started that game in the hole
+    assert_eq!(played[1].home_team.id, away);
+    assert_eq!(played[1].home_team.score, -shamed_by);
+    assert_eq!(played[1].away_team.score, 0.0);
+    assert_eq!(world.team(away).shame_runs, 0.0);
+}
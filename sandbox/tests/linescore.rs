@@ -0,0 +1,70 @@
+use sandbox::{
+    entities::World,
+    events::Event,
+    mods::{Mod, ModLifetime},
+    rng::Rng,
+    Game, Weather,
+};
+use uuid::Uuid;
+
+mod common;
+
+//a home run for whoever's up, by the first batter in their lineup
+fn homer(game: &mut Game, world: &mut World) {
+    let team = game.scoreboard.batting_team().id;
+    Event::BatterUp { batter: world.team(team).lineup[0] }.apply(game, world);
+    Event::HomeRun.apply(game, world);
+}
+
+fn salmon_game() -> (World, Game, Uuid, Uuid) {
+    let (mut world, home, away) = common::world();
+    world.team_mut(home).mods.add(Mod::HomeFieldAdvantage, ModLifetime::Permanent);
+    let game = Game::new(home, away, 0, Some(Weather::Salmon), &world, &mut Rng::new(1, 2));
+    (world, game, home, away)
+}
+
+#[test]
+fn home_field_advantage_is_inning_zero() {
+    let (_, game, _, _) = salmon_game();
+    assert_eq!(game.scoreboard.home_team.score, 1.0);
+    assert_eq!(game.linescore.runs_in(0, false), 1.0);
+    assert_eq!(game.linescore.runs_in(0, true), 0.0);
+    assert_eq!(game.linescore.runs(false), game.scoreboard.home_team.score);
+    //and nobody's batted yet
+    assert_eq!(game.linescore.runs_in(1, true), 0.0);
+    assert!(game.linescore.inning(1, false).is_none());
+}
+
+#[test]
+fn salmon_rewinds_the_first_inning_but_not_the_start() {
+    let (mut world, mut game, _, _) = salmon_game();
+    homer(&mut game, &mut world);
+    Event::InningSwitch { inning: 1, top: false }.apply(&mut game, &mut world);
+    homer(&mut game, &mut world);
+    Event::InningSwitch { inning: 2, top: true }.apply(&mut game, &mut world);
+    assert_eq!((game.scoreboard.away_team.score, game.scoreboard.home_team.score), (1.0, 2.0));
+
+    Event::Salmon { away_runs_lost: true, home_runs_lost: true }.apply(&mut game, &mut world);
+    assert_eq!((game.inning, game.scoreboard.top), (1, true));
+    //both homers are gone, the home field advantage run stays
+    assert_eq!((game.scoreboard.away_team.score, game.scoreboard.home_team.score), (0.0, 1.0));
+    assert_eq!(game.linescore.runs_in(1, true), 0.0);
+    assert_eq!(game.linescore.runs_in(1, false), 0.0);
+    assert_eq!(game.linescore.runs_in(0, false), 1.0);
+    assert_eq!(game.linescore.runs(false), game.scoreboard.home_team.score);
+    //the hits still happened
+    assert_eq!((game.linescore.hits(true), game.linescore.hits(false)), (1, 1));
+}
+
+#[test]
+fn salmon_after_a_top_half_only_takes_the_away_runs() {
+    let (mut world, mut game, _, _) = salmon_game();
+    homer(&mut game, &mut world);
+    homer(&mut game, &mut world);
+    Event::InningSwitch { inning: 1, top: false }.apply(&mut game, &mut world);
+    Event::Salmon { away_runs_lost: true, home_runs_lost: false }.apply(&mut game, &mut world);
+    assert_eq!((game.inning, game.scoreboard.top), (1, true));
+    assert_eq!((game.scoreboard.away_team.score, game.scoreboard.home_team.score), (0.0, 1.0));
+    assert_eq!(game.linescore.runs(true), 0.0);
+    assert_eq!(game.linescore.hits(true), 2);
+}
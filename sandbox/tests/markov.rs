@@ -0,0 +1,48 @@
+use sandbox::{entities::World, markov::Model, rng::Rng, Game, Weather};
+use uuid::Uuid;
+
+mod common;
+
+fn game(world: &World, home: Uuid, away: Uuid) -> Game {
+    Game::new(home, away, 0, Some(Weather::Sun), world, &mut Rng::new(1, 2))
+}
+
+#[test]
+fn even_teams_are_a_coin_flip() {
+    let (world, home, away) = common::mirrored();
+    let game = game(&world, home, away);
+    let model = Model::new(&game, &world);
+    let p = model.win_probability(&game, &world);
+    assert!((p - 0.5).abs() < 0.01, "{}", p);
+}
+
+#[test]
+fn more_runs_and_fewer_outs_are_better() {
+    let (world, home, away) = common::world();
+    let mut game = game(&world, home, away);
+    let model = Model::new(&game, &world);
+
+    //going into the bottom of the fifth, every extra run for the home team helps them
+    game.inning = 5;
+    game.scoreboard.top = false;
+    let mut last = 0.0;
+    for lead in -4..=4 {
+        game.scoreboard.away_team.score = 4.0;
+        game.scoreboard.home_team.score = 4.0 + lead as f64;
+        let p = model.win_probability(&game, &world);
+        assert!(p > last, "home up {}: {} after {}", lead, p, last);
+        last = p;
+    }
+
+    //down one in the bottom of the ninth, every out hurts them
+    game.inning = 9;
+    game.scoreboard.away_team.score = 3.0;
+    game.scoreboard.home_team.score = 2.0;
+    let mut last = 1.0;
+    for outs in 0..3 {
+        game.outs = outs;
+        let p = model.win_probability(&game, &world);
+        assert!(p < last, "{} outs: {} after {}", outs, p, last);
+        last = p;
+    }
+}
@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use sandbox::mods::{Mod, ModHolder, ModLifetime, Mods};
+use strum::{EnumCount, IntoEnumIterator};
+
+mod common;
+
+#[test]
+fn every_mod_round_trips_through_its_attribute() {
+    assert_eq!(Mod::iter().count(), Mod::COUNT);
+    for m in Mod::iter() {
+        let name: &'static str = m.into();
+        assert_eq!(Mod::from_str(name), Ok(m), "{}", name);
+
+        let mut mods = Mods::new();
+        assert!(mods.add_attr(name, ModLifetime::Season));
+        assert!(mods.has(m));
+        assert_eq!(mods.attrs(ModLifetime::Season), vec![name.to_string()]);
+        //nobody else shares its bit
+        assert!(Mod::iter().filter(|&other| other != m).all(|other| !mods.has(other)), "{}", name);
+        mods.remove(m);
+        assert!(!mods.has(m));
+    }
+}
+
+#[test]
+fn every_mod_has_metadata() {
+    for m in Mod::iter() {
+        let info = m.info();
+        assert!(!info.name.is_empty() && !info.description.is_empty(), "{:?}", m);
+    }
+    assert_eq!(Mod::Traveling.info().holder, ModHolder::Team);
+    assert_eq!(Mod::Flinch.info().holder, ModHolder::Player);
+    assert_eq!(Mod::FourthStrike.info().holder, ModHolder::Both);
+}
+
+#[test]
+fn adding_a_group_member_evicts_the_others() {
+    for (first, second) in [(Mod::HeatingUp, Mod::RedHot), (Mod::Wired, Mod::Tired), (Mod::Overperforming, Mod::Underperforming)] {
+        let mut mods = Mods::new();
+        mods.add(Mod::Flinch, ModLifetime::Permanent);
+        //whatever lifetime the other one had
+        mods.add(first, ModLifetime::Season);
+        mods.add(second, ModLifetime::Game);
+        assert!(!mods.has(first) && mods.has(second), "{:?}", second);
+        mods.add(first, ModLifetime::Permanent);
+        assert!(mods.has(first) && !mods.has(second), "{:?}", first);
+        //and nothing outside the group
+        assert!(mods.has(Mod::Flinch));
+    }
+}
+
+#[test]
+fn team_mods_are_cleared_with_their_lifetime() {
+    let (mut world, home, _) = common::world();
+    let mods = &mut world.team_mut(home).mods;
+    mods.add(Mod::Traveling, ModLifetime::Game);
+    mods.add(Mod::Growth, ModLifetime::Week);
+    mods.add(Mod::Sealant, ModLifetime::Season);
+    mods.add(Mod::HomeFieldAdvantage, ModLifetime::Permanent);
+
+    world.clear_game();
+    let mods = &world.team(home).mods;
+    assert!(!mods.has(Mod::Traveling));
+    assert!(mods.has(Mod::Growth) && mods.has(Mod::Sealant) && mods.has(Mod::HomeFieldAdvantage));
+
+    world.clear_weekly();
+    let mods = &world.team(home).mods;
+    assert!(!mods.has(Mod::Growth));
+    assert!(mods.has(Mod::Sealant) && mods.has(Mod::HomeFieldAdvantage));
+
+    world.clear_season();
+    let mods = &world.team(home).mods;
+    assert!(!mods.has(Mod::Sealant));
+    assert!(mods.has(Mod::HomeFieldAdvantage));
+}
@@ -0,0 +1,75 @@
+use sandbox::{
+    entities::{AttrCategory, PlayerAttr, World},
+    mods::{Mod, ModLifetime},
+    multipliers::{combine, multiplier, Combine, Contribution, Owner},
+    rng::Rng,
+    Game, Weather,
+};
+
+mod common;
+
+#[test]
+fn traveling_and_overperforming_stack_additively() {
+    let (mut world, home, away) = common::world();
+    let batter = world.team(away).lineup[0];
+    let game = Game::new(home, away, 0, Some(Weather::Sun), &world, &mut Rng::new(1, 2));
+    //moxie isn't one of the stats traveling leaves alone
+    let moxie = |world: &World| {
+        let data = game.half_multiplier_data(world, true);
+        multiplier(PlayerAttr::Moxie, &world.player(batter).mods, &world.team(away).mods, &data)
+    };
+    assert_eq!(moxie(&world), 1.0);
+
+    world.player_mut(batter).mods.add(Mod::Overperforming, ModLifetime::Permanent);
+    assert!((moxie(&world) - 1.2).abs() < 1e-9);
+
+    //the away team batting in the top gets the traveling bonus on top, summed rather than multiplied
+    world.team_mut(away).mods.add(Mod::Traveling, ModLifetime::Permanent);
+    assert!((moxie(&world) - 1.25).abs() < 1e-9);
+
+    world.player_mut(batter).mods.remove(Mod::Overperforming);
+    assert!((moxie(&world) - 1.05).abs() < 1e-9);
+}
+
+//none of the real ones multiply yet, so this table makes one up: overperforming as usual,
+//plus a team mod that doubles whatever the player's stats come to
+const TABLE: [Contribution; 2] = [
+    Contribution {
+        source: Mod::Overperforming,
+        owner: Owner::Player,
+        combine: Combine::Additive,
+        categories: &[AttrCategory::Batting],
+        amount: |_, _| 0.2,
+    },
+    Contribution {
+        source: Mod::Growth,
+        owner: Owner::Team,
+        combine: Combine::Multiplicative,
+        categories: &[AttrCategory::Batting],
+        amount: |_, _| 2.0,
+    },
+];
+
+#[test]
+fn multiplicative_contributions_scale_the_sum() {
+    let (mut world, home, away) = common::world();
+    let batter = world.team(away).lineup[0];
+    let game = Game::new(home, away, 0, Some(Weather::Sun), &world, &mut Rng::new(1, 2));
+    let moxie = |world: &World| {
+        let data = game.half_multiplier_data(world, true);
+        combine(&TABLE, PlayerAttr::Moxie, &world.player(batter).mods, &world.team(away).mods, &data)
+    };
+    assert_eq!(moxie(&world), 1.0);
+
+    world.team_mut(away).mods.add(Mod::Growth, ModLifetime::Permanent);
+    assert_eq!(moxie(&world), 2.0);
+
+    //(1 + 0.2) * 2, not 1 + 0.2 + 2
+    world.player_mut(batter).mods.add(Mod::Overperforming, ModLifetime::Permanent);
+    assert!((moxie(&world) - 2.4).abs() < 1e-9);
+
+    //and only for the categories it's listed under
+    let data = game.half_multiplier_data(&world, true);
+    let omni = combine(&TABLE, PlayerAttr::Omniscience, &world.player(batter).mods, &world.team(away).mods, &data);
+    assert_eq!(omni, 1.0);
+}
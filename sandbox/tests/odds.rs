@@ -0,0 +1,45 @@
+use sandbox::{entities::{PlayerAttr, World}, odds::home_odds, rng::Rng, Game, Weather};
+use uuid::Uuid;
+
+mod common;
+
+fn game(world: &World, home: Uuid, away: Uuid) -> Game {
+    Game::new(home, away, 0, Some(Weather::Sun), world, &mut Rng::new(1, 2))
+}
+
+#[test]
+fn mirrored_teams_are_even() {
+    let (world, home, away) = common::mirrored();
+    let odds = home_odds(&game(&world, home, away), &world);
+    assert!((odds - 0.5).abs() < 1e-9, "{}", odds);
+}
+
+#[test]
+fn swapping_sides_flips_the_odds() {
+    let (world, home, away) = common::world();
+    let odds = home_odds(&game(&world, home, away), &world);
+    let swapped = home_odds(&game(&world, away, home), &world);
+    assert!((0.0..=1.0).contains(&odds));
+    assert!((odds + swapped - 1.0).abs() < 1e-9, "{} {}", odds, swapped);
+}
+
+#[test]
+fn better_hitters_are_favored() {
+    let (mut world, home, away) = common::mirrored();
+    for id in world.team(home).lineup.clone() {
+        let player = world.player_mut(id);
+        for attr in [PlayerAttr::Thwackability, PlayerAttr::Moxie, PlayerAttr::Divinity, PlayerAttr::Musclitude] {
+            player.set(attr, player.get(attr) + 0.5);
+        }
+    }
+    let odds = home_odds(&game(&world, home, away), &world);
+    assert!(odds > 0.5, "{}", odds);
+}
+
+#[test]
+fn a_head_start_counts() {
+    let (world, home, away) = common::mirrored();
+    let mut game = game(&world, home, away);
+    game.scoreboard.home_team.score = 1.0;
+    assert!(home_odds(&game, &world) > 0.5);
+}
@@ -0,0 +1,93 @@
+use sandbox::{
+    entities::Player,
+    events::Event,
+    plate_appearance::{plate_appearance, PaOutcome, Situation},
+    rng::Rng,
+    ruleset::PluginKind,
+    sim::Sim,
+    Game, Weather,
+};
+
+mod common;
+
+const PLATE_APPEARANCES: usize = 20000;
+
+fn outcome(event: &Event) -> Option<PaOutcome> {
+    Some(match event {
+        Event::Walk => PaOutcome::Walk,
+        Event::Strikeout => PaOutcome::Strikeout,
+        Event::HomeRun => PaOutcome::HomeRun,
+        Event::BaseHit { bases: 4, .. } => PaOutcome::Quadruple,
+        Event::BaseHit { bases: 3, .. } => PaOutcome::Triple,
+        Event::BaseHit { bases: 2, .. } => PaOutcome::Double,
+        Event::BaseHit { .. } => PaOutcome::Single,
+        Event::Flyout { .. } => PaOutcome::Flyout,
+        Event::GroundOut { .. } => PaOutcome::GroundOut,
+        Event::DoublePlay { .. } => PaOutcome::DoublePlay,
+        Event::FieldersChoice { .. } => PaOutcome::FieldersChoice,
+        _ => return None,
+    })
+}
+
+//plays the same plate appearance over and over with nothing but the base pitch plugin,
+//and checks how often each outcome came up against what plate_appearance says
+fn compare(outs: i16, runner_on_first: bool) {
+    let (mut world, home, away) = common::world();
+    world.ruleset.plugins = vec![PluginKind::Base];
+    let batter = world.team(away).lineup[2];
+    let runner = world.team(away).lineup[3];
+    let mut game = Game::new(home, away, 0, Some(Weather::Sun), &world, &mut Rng::new(1, 2));
+    game.assign_batter(batter);
+    let reset = |game: &mut Game| {
+        game.balls = 0;
+        game.strikes = 0;
+        game.outs = outs;
+        game.runners.clear();
+        if runner_on_first {
+            game.runners.add(0, runner, game.pitcher());
+        }
+    };
+    reset(&mut game);
+
+    let expected = {
+        let data = game.compute_multiplier_data(&world);
+        let fielders: Vec<&Player> = world.team(home).lineup.iter().map(|&id| world.player(id)).collect();
+        plate_appearance(world.player(game.pitcher()), world.player(batter), &fielders, &Situation::from_game(&game, &world), &world.ruleset, &data).outcomes
+    };
+
+    let mut rng = Rng::new(69, 420);
+    let mut sim = Sim::new(&mut world, &mut rng);
+    let mut counts = [0usize; 11];
+    for _ in 0..PLATE_APPEARANCES {
+        loop {
+            let event = sim.next(&game);
+            match outcome(&event) {
+                Some(outcome) => {
+                    counts[outcome.index()] += 1;
+                    break;
+                },
+                None => event.apply(&mut game, sim.world),
+            }
+        }
+        reset(&mut game);
+    }
+
+    for outcome in PaOutcome::ALL {
+        let p = expected[outcome.index()];
+        let seen = counts[outcome.index()] as f64 / PLATE_APPEARANCES as f64;
+        //four standard errors, plus a little for outcomes that hardly ever happen
+        let slack = 4.0 * (p * (1.0 - p) / PLATE_APPEARANCES as f64).sqrt() + 1e-3;
+        assert!((seen - p).abs() < slack, "{:?}: expected {:.4}, saw {:.4}", outcome, p, seen);
+    }
+    assert!((expected.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn bases_empty_matches_do_pitch() {
+    compare(0, false);
+}
+
+#[test]
+fn runner_on_first_matches_do_pitch() {
+    compare(1, true);
+}
@@ -0,0 +1,45 @@
+use sandbox::{
+    entities::{Blood, Coffee, Player},
+    rng::{Replay, Rng},
+};
+
+//seeds depend on new players taking their rolls in exactly this order
+#[test]
+fn new_players_roll_in_order() {
+    let mut rolls: Vec<f64> = (0..26).map(|i| (i + 1) as f64 / 100.0).collect();
+    rolls.extend([
+        0.55, //soul
+        0.3, //allergy
+        0.425, //fate
+        0.99, //ritual
+        0.1, //blood
+        0.2, //coffee
+        1.5 / 532.0, //first name
+        2.5 / 538.0, //last name
+    ]);
+    let mut replay = Replay::new(rolls);
+    let player = Player::new(&mut replay);
+    assert_eq!(replay.position(), 34);
+    assert!(!replay.exhausted());
+
+    for (i, attr) in Player::ROLL_ORDER.into_iter().enumerate() {
+        assert_eq!(player.get(attr), (i + 1) as f64 / 100.0, "{:?}", attr);
+    }
+    assert_eq!(player.soul, 6);
+    assert_eq!(player.peanut_allergy, Some(true));
+    assert_eq!(player.fate, 42);
+    assert_eq!(player.ritual, "");
+    assert_eq!(player.blood, Some(Blood::ALL[1]));
+    assert_eq!(player.coffee, Some(Coffee::ALL[2]));
+    let first = include_str!("../src/firstnames.txt").split_whitespace().nth(1).unwrap();
+    let last = include_str!("../src/lastnames.txt").split_whitespace().nth(2).unwrap();
+    assert_eq!(player.name, format!("{} {}", first, last));
+}
+
+#[test]
+fn the_same_seed_makes_the_same_player() {
+    let a = Player::new(&mut Rng::new(69, 420));
+    let b = Player::new(&mut Rng::new(69, 420));
+    assert_eq!(a.stats, b.stats);
+    assert_eq!((&a.name, a.soul, a.fate, a.peanut_allergy), (&b.name, b.soul, b.fate, b.peanut_allergy));
+}
@@ -0,0 +1,31 @@
+use sandbox::rng::{Replay, Rng, Roll};
+
+#[test]
+fn rng_rolls_the_same_either_way() {
+    let (mut direct, mut through_roll) = (Rng::new(69, 420), Rng::new(69, 420));
+    for _ in 0..100 {
+        assert_eq!(direct.next(), Roll::next(&mut through_roll));
+    }
+}
+
+#[test]
+fn replay_hands_out_recorded_rolls_in_order() {
+    let mut replay = Replay::new(vec![0.25, 0.5, 0.75]);
+    assert_eq!(replay.next(), 0.25);
+    assert_eq!(replay.index(10), 5);
+    assert_eq!((replay.position(), replay.remaining()), (2, 1));
+    assert_eq!(replay.next(), 0.75);
+    //all of them used is fine
+    assert!(!replay.exhausted());
+    assert_eq!(replay.remaining(), 0);
+}
+
+#[test]
+fn running_out_of_rolls_is_flagged_not_a_panic() {
+    let mut replay = Replay::new(vec![0.5]);
+    replay.next();
+    assert_eq!(replay.next(), 0.0);
+    replay.next();
+    assert!(replay.exhausted());
+    assert_eq!((replay.position(), replay.recorded(), replay.remaining()), (3, 1, 0));
+}
@@ -0,0 +1,107 @@
+use sandbox::{
+    entities::World,
+    events::Event,
+    mods::{Mod, ModLifetime},
+    rng::Rng,
+    rotation,
+    Game, Weather,
+};
+use uuid::Uuid;
+
+mod common;
+use common::world;
+
+fn finish(world: &mut World, home: Uuid, away: Uuid) -> Game {
+    let mut game = Game::new(home, away, 0, Some(Weather::Sun), world, &mut Rng::new(1, 2));
+    game.inning = 9;
+    game.scoreboard.top = false;
+    game.outs = 3;
+    game.scoreboard.away_team.score = 1.0;
+    Event::GameOver.apply(&mut game, world);
+    game
+}
+
+#[test]
+fn starters_go_in_order() {
+    let (mut world, home, away) = world();
+    let rotation = world.team(home).rotation.clone();
+    //around the rotation twice, one spot per game
+    for day in 0..2 * rotation.len() {
+        let game = finish(&mut world, home, away);
+        assert_eq!(game.scoreboard.home_team.pitcher, rotation[day % rotation.len()]);
+    }
+    assert_eq!(world.team(home).rotation_slot, 0);
+}
+
+#[test]
+fn shelled_starters_give_way_to_the_day_before() {
+    let (mut world, home, away) = world();
+    let rotation = world.team(home).rotation.clone();
+    let last = rotation.len() - 1;
+    //on opening day the spot before the first is the last one
+    world.player_mut(rotation[0]).mods.add(Mod::Shelled, ModLifetime::Permanent);
+    assert_eq!(rotation::slotted(&world, home), rotation[0]);
+    assert_eq!(rotation::starter(&world, home), rotation[last]);
+    //then the rotation carries on from the shelled pitcher's spot, one a game
+    let starters: Vec<Uuid> = (0..3).map(|_| finish(&mut world, home, away).scoreboard.home_team.pitcher).collect();
+    assert_eq!(starters, vec![rotation[last], rotation[1], rotation[2]]);
+    world.team_mut(home).rotation_slot = 3;
+    world.player_mut(rotation[3]).mods.add(Mod::Shelled, ModLifetime::Permanent);
+    assert_eq!(rotation::starter(&world, home), rotation[2]);
+}
+
+#[test]
+fn only_shelled_starters_are_replaced() {
+    let (mut world, home, _) = world();
+    let rotation = world.team(home).rotation.clone();
+    world.player_mut(rotation[0]).mods.add(Mod::Elsewhere, ModLifetime::Permanent);
+    assert_eq!(rotation::starter(&world, home), rotation[0]);
+    //and it's the day before whether or not they're shelled too
+    for &pitcher in rotation.iter() {
+        world.player_mut(pitcher).mods.add(Mod::Shelled, ModLifetime::Permanent);
+    }
+    world.team_mut(home).rotation_slot = 2;
+    assert_eq!(rotation::starter(&world, home), rotation[1]);
+}
+
+#[test]
+fn replacing_keeps_the_spot() {
+    let (mut world, home, _) = world();
+    let rotation = world.team(home).rotation.clone();
+    let shadow = world.team(home).shadows[0];
+    assert!(rotation::replace(world.team_mut(home), rotation[3], shadow));
+    assert_eq!(world.team(home).rotation[3], shadow);
+    world.team_mut(home).rotation_slot = 3;
+    assert_eq!(rotation::starter(&world, home), shadow);
+    //someone who was never in the rotation leaves it alone
+    assert!(!rotation::replace(world.team_mut(home), rotation[3], rotation[0]));
+    assert_eq!(world.team(home).rotation[3], shadow);
+}
+
+#[test]
+fn feedback_swaps_keep_the_spot() {
+    let (mut world, home, away) = world();
+    let rotation = world.team(home).rotation.clone();
+    let other = world.team(away).rotation[0];
+    world.team_mut(home).rotation_slot = 1;
+    world.swap(rotation[1], other);
+    assert_eq!(world.team(home).rotation[1], other);
+    assert_eq!(world.team(away).rotation[0], rotation[1]);
+    //finishing the game with the swapped-in pitcher moves the slot past the spot they took
+    rotation::advance(&mut world, home);
+    assert_eq!(rotation::starter(&world, home), rotation[2]);
+}
+
+#[test]
+fn reverb_shuffles_around_the_slot() {
+    let (mut world, home, _) = world();
+    let rotation = world.team(home).rotation.clone();
+    world.team_mut(home).rotation_slot = 1;
+    //a rotation-only reverb that reverses it
+    let changes: Vec<usize> = (0..rotation.len()).rev().collect();
+    world.team_mut(home).apply_reverb_changes(3, &changes);
+    let reversed: Vec<Uuid> = rotation.iter().rev().copied().collect();
+    assert_eq!(world.team(home).rotation, reversed);
+    assert_eq!(world.team(home).rotation_slot, 1);
+    assert_eq!(rotation::starter(&world, home), reversed[1]);
+}
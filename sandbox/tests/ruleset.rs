@@ -0,0 +1,21 @@
+use sandbox::ruleset::{Ruleset, RulesetError};
+
+#[test]
+fn only_complete_eras_make_a_ruleset() {
+    assert!(Ruleset::new(11).is_ok());
+    //formulas but no weather table or plugin order
+    for season in 12..=14 {
+        assert!(matches!(Ruleset::new(season), Err(RulesetError::Incomplete { .. })), "season {}", season);
+    }
+    for season in [0, 10, 15, 23] {
+        assert!(matches!(Ruleset::new(season), Err(RulesetError::UnsupportedSeason(s)) if s == season));
+    }
+}
+
+#[test]
+fn flooding_turns_up_late_in_the_season() {
+    let ruleset = Ruleset::new(11).unwrap();
+    let late_from = ruleset.weather.late_from;
+    assert_eq!(ruleset.weather_weights(late_from - 1).len() + 1, ruleset.weather_weights(late_from).len());
+    assert_eq!(ruleset.weather_weights(0), ruleset.weather_weights(late_from - 1));
+}
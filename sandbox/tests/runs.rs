@@ -0,0 +1,47 @@
+use sandbox::{events::Event, rng::Rng, Game, Weather};
+use uuid::Uuid;
+
+mod common;
+
+//(runner, rbi batter, charged pitcher, the event's name) for every run so far
+fn runs(game: &Game) -> Vec<(Uuid, Option<Uuid>, Uuid, &'static str)> {
+    game.runs.iter().map(|run| (run.runner, run.batter, run.pitcher, game.events.get(run.event))).collect()
+}
+
+#[test]
+fn runs_are_credited_through_a_game() {
+    let (mut world, home, away) = common::world();
+    let mut game = Game::new(home, away, 0, Some(Weather::Sun), &world, &mut Rng::new(1, 2));
+    let lineup = world.team(away).lineup.clone();
+    let starter = game.pitcher();
+    //the runners got on against a pitcher who's since been taken out
+    let earlier = world.team(home).rotation.iter().copied().find(|&p| p != starter).unwrap();
+    for base in 0..3 {
+        game.runners.add(base, lineup[base as usize + 1], earlier);
+    }
+
+    //bases loaded walk: the runner on third comes in, the batter gets the rbi and the earlier pitcher the run
+    Event::BatterUp { batter: lineup[0] }.apply(&mut game, &mut world);
+    Event::Walk.apply(&mut game, &mut world);
+    let walk = game.events.len() - 1;
+    assert_eq!(runs(&game), vec![(lineup[3], Some(lineup[0]), earlier, "Walk")]);
+    assert_eq!(game.runs[0].event, walk);
+    assert_eq!(game.scoreboard.away_team.score, 1.0);
+
+    //grand slam: the two left from before are still on the earlier pitcher, the walked batter and the hitter are on the starter
+    Event::BatterUp { batter: lineup[4] }.apply(&mut game, &mut world);
+    Event::HomeRun.apply(&mut game, &mut world);
+    let homer = game.events.len() - 1;
+    let mut slam = runs(&game)[1..].to_vec();
+    slam.sort();
+    let mut expected = vec![
+        (lineup[0], Some(lineup[4]), starter, "HomeRun"),
+        (lineup[1], Some(lineup[4]), earlier, "HomeRun"),
+        (lineup[2], Some(lineup[4]), earlier, "HomeRun"),
+        (lineup[4], Some(lineup[4]), starter, "HomeRun"),
+    ];
+    expected.sort();
+    assert_eq!(slam, expected);
+    assert!(game.runs[1..].iter().all(|run| run.event == homer));
+    assert_eq!(game.scoreboard.away_team.score, 5.0);
+}
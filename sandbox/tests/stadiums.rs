@@ -0,0 +1,66 @@
+use sandbox::{entities::{Stadium, World}, formulas, rng::Rng, Game, Weather};
+use uuid::Uuid;
+
+mod common;
+
+fn park(team: Uuid, stat: f64) -> Stadium {
+    Stadium {
+        id: Uuid::new_v4(),
+        team: Some(team),
+        name: "Park".to_string(),
+        nickname: "Park".to_string(),
+        mysticism: stat,
+        viscosity: stat,
+        elongation: stat,
+        filthiness: stat,
+        obtuseness: stat,
+        forwardness: stat,
+        grandiosity: stat,
+        ominousness: stat,
+        fortification: stat,
+        inconvenience: stat,
+        hype: 0.0,
+    }
+}
+
+fn build(world: &mut World, team: Uuid, stadium: Stadium) {
+    world.team_mut(team).stadium = Some(stadium.id);
+    world.insert_stadium(stadium);
+}
+
+//strike, foul and home run chances for the away leadoff hitter against the home starter
+fn thresholds(world: &World, game: &Game) -> [f64; 3] {
+    let (batter, pitcher) = (world.team(game.scoreboard.away_team.id).lineup[0], game.scoreboard.home_team.pitcher);
+    let (batter, pitcher) = (world.player(batter), world.player(pitcher));
+    let data = game.half_multiplier_data(world, true);
+    [
+        formulas::strike_threshold(pitcher, batter, false, &world.ruleset, &data),
+        formulas::foul_threshold(pitcher, batter, &world.ruleset, &data),
+        formulas::hr_threshold(pitcher, batter, &world.ruleset, &data),
+    ]
+}
+
+#[test]
+fn an_average_park_plays_like_no_park() {
+    let (mut world, home, away) = common::world();
+    let game = Game::new(home, away, 0, Some(Weather::Sun), &world, &mut Rng::new(1, 2));
+    let without = thresholds(&world, &game);
+    build(&mut world, home, park(home, 0.5));
+    assert_eq!(thresholds(&world, &game), without);
+}
+
+#[test]
+fn the_home_teams_park_is_the_one_played_in() {
+    let (mut world, home, away) = common::world();
+    let game = Game::new(home, away, 0, Some(Weather::Sun), &world, &mut Rng::new(1, 2));
+    let without = thresholds(&world, &game);
+    build(&mut world, away, park(away, 1.0));
+    assert_eq!(thresholds(&world, &game), without);
+
+    build(&mut world, home, park(home, 1.0));
+    let [strike, foul, hr] = thresholds(&world, &game);
+    //forwardness helps the pitcher find the zone, grandiosity and fortification keep the ball in
+    assert!(strike > without[0]);
+    assert!((foul - without[1]).abs() < 1e-9, "forwardness and obtuseness cancel out");
+    assert!(hr < without[2]);
+}
@@ -0,0 +1,240 @@
+use sandbox::{game_end, Game};
+use uuid::Uuid;
+
+//betting on the regular season the way fans did, against the pregame odds from Game::odds
+
+//a new fan's balance and bet limit, before any shop upgrades
+pub const STARTING_COINS: f64 = 250.0;
+pub const MAX_BET: f64 = 20.0;
+
+//what a winning bet returns per coin, bet included. the game never published its payouts, so these
+//are a fit to what fans were paid out on their bets, not anything from the game's code.
+//both halves meet at 2 for even odds
+pub fn payout(odds: f64) -> f64 {
+    if odds >= 0.5 {
+        2.0 - 0.000335 * (100.0 * (odds - 0.5)).powf(2.045)
+    } else {
+        2.0 + 0.0015 * (100.0 * (0.5 - odds)).powf(2.2)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Strategy {
+    Favorite,
+    Underdog,
+    Home,
+    Away,
+    //only bet when the odds say a coin is worth at least this much more than it costs
+    Value(f64),
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 5] = [
+        Strategy::Favorite,
+        Strategy::Underdog,
+        Strategy::Home,
+        Strategy::Away,
+        Strategy::Value(0.05),
+    ];
+
+    //whether to back the home team, or none to sit the game out
+    fn pick(&self, home_odds: f64) -> Option<bool> {
+        let edge = |odds: f64| odds * payout(odds) - 1.0;
+        match *self {
+            Strategy::Favorite => Some(home_odds >= 0.5),
+            Strategy::Underdog => Some(home_odds < 0.5),
+            Strategy::Home => Some(true),
+            Strategy::Away => Some(false),
+            Strategy::Value(min_edge) => {
+                let home = edge(home_odds);
+                let away = edge(1.0 - home_odds);
+                if home.max(away) < min_edge {
+                    None
+                } else {
+                    Some(home >= away)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Bet {
+    game: Uuid,
+    team: Uuid,
+    amount: f64,
+    payout: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Bettor {
+    pub strategy: Strategy,
+    pub coins: f64,
+    pub max_bet: f64,
+    bets: Vec<Bet>,
+    pub placed: usize,
+    pub wins: usize,
+    pub wagered: f64,
+    pub returned: f64,
+    //what the bets were worth if the odds are right
+    pub expected: f64,
+}
+
+impl Bettor {
+    pub fn new(strategy: Strategy, coins: f64, max_bet: f64) -> Bettor {
+        Bettor {
+            strategy,
+            coins,
+            max_bet,
+            bets: Vec::new(),
+            placed: 0,
+            wins: 0,
+            wagered: 0.0,
+            returned: 0.0,
+            expected: 0.0,
+        }
+    }
+
+    //before the game starts, once the pitchers are set. home_odds is Game::odds
+    pub fn bet(&mut self, game: &Game, home_odds: f64) {
+        let amount = self.max_bet.min(self.coins).floor();
+        if amount <= 0.0 {
+            return;
+        }
+        let home = match self.strategy.pick(home_odds) {
+            Some(home) => home,
+            None => return,
+        };
+        let (team, odds) = if home {
+            (game.scoreboard.home_team.id, home_odds)
+        } else {
+            (game.scoreboard.away_team.id, 1.0 - home_odds)
+        };
+        let payout = payout(odds);
+        self.coins -= amount;
+        self.wagered += amount;
+        self.expected += amount * (odds * payout - 1.0);
+        self.placed += 1;
+        self.bets.push(Bet { game: game.id, team, amount, payout });
+    }
+
+    //after the game is over
+    pub fn settle(&mut self, game: &Game) {
+        let (winner, _) = game_end::result(game);
+        if let Some(i) = self.bets.iter().position(|bet| bet.game == game.id) {
+            let bet = self.bets.swap_remove(i);
+            if bet.team == winner {
+                let winnings = (bet.amount * bet.payout).round();
+                self.coins += winnings;
+                self.returned += winnings;
+                self.wins += 1;
+            }
+        }
+    }
+}
+
+//adds up a season's bettor into the running totals, final balance included
+pub fn merge(totals: &mut Vec<Bettor>, season: &[Bettor]) {
+    if totals.is_empty() {
+        totals.extend(season.iter().map(|bettor| Bettor::new(bettor.strategy, 0.0, bettor.max_bet)));
+    }
+    for (total, bettor) in totals.iter_mut().zip(season.iter()) {
+        total.coins += bettor.coins;
+        total.placed += bettor.placed;
+        total.wins += bettor.wins;
+        total.wagered += bettor.wagered;
+        total.returned += bettor.returned;
+        total.expected += bettor.expected;
+    }
+}
+
+//totals for every strategy over all the seasons
+pub fn report(bettors: &[Bettor], seasons: usize) {
+    println!("betting ({} season{}):", seasons, if seasons == 1 { "" } else { "s" });
+    println!("{:<14} {:>6} {:>7} {:>10} {:>10} {:>10}", "strategy", "bets", "won", "expected", "actual", "coins");
+    for bettor in bettors.iter() {
+        let per_coin = |x: f64| if bettor.wagered > 0.0 { x / bettor.wagered } else { 0.0 };
+        let strategy = match bettor.strategy {
+            Strategy::Value(min_edge) => format!("Value({})", min_edge),
+            strategy => format!("{:?}", strategy),
+        };
+        println!(
+            "{:<14} {:>6} {:>6.1}% {:>+10.4} {:>+10.4} {:>10.0}",
+            strategy,
+            bettor.placed / seasons,
+            if bettor.placed > 0 { 100.0 * bettor.wins as f64 / bettor.placed as f64 } else { 0.0 },
+            per_coin(bettor.expected),
+            per_coin(bettor.returned - bettor.wagered),
+            bettor.coins / seasons as f64
+        );
+    }
+    println!("(expected and actual are per coin bet, coins is the average final balance)");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sandbox::{entities::World, rng::Rng, ruleset::Ruleset, Weather};
+
+    fn game() -> Game {
+        let mut rng = Rng::new(69, 420);
+        let mut world = World::new(Ruleset::new(11).unwrap());
+        let away = world.gen_team(&mut rng, "Away".to_string(), "A".to_string());
+        let home = world.gen_team(&mut rng, "Home".to_string(), "H".to_string());
+        Game::new(home, away, 0, Some(Weather::Sun), &world, &mut rng)
+    }
+
+    #[test]
+    fn payouts_are_even_at_even_odds() {
+        assert_eq!(payout(0.5), 2.0);
+        //and the two halves of the fit meet there
+        assert!((payout(0.5 - 1e-9) - 2.0).abs() < 1e-6);
+        assert!((payout(0.5 + 1e-9) - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn favorites_pay_less() {
+        let odds: Vec<f64> = (1..100).map(|i| i as f64 / 100.0).collect();
+        for pair in odds.windows(2) {
+            assert!(payout(pair[0]) > payout(pair[1]), "{} {}", pair[0], pair[1]);
+        }
+        assert!(payout(0.99) > 1.0);
+    }
+
+    #[test]
+    fn settling_pays_out_winners() {
+        let mut game = game();
+        let mut home = Bettor::new(Strategy::Home, STARTING_COINS, MAX_BET);
+        let mut away = Bettor::new(Strategy::Away, STARTING_COINS, MAX_BET);
+        //even odds have no edge to bet on
+        let mut value = Bettor::new(Strategy::Value(0.05), STARTING_COINS, MAX_BET);
+        for bettor in [&mut home, &mut away, &mut value] {
+            bettor.bet(&game, 0.5);
+        }
+        assert_eq!((home.coins, away.coins, value.coins), (230.0, 230.0, 250.0));
+        assert_eq!(value.placed, 0);
+
+        game.scoreboard.home_team.score = 3.0;
+        game.scoreboard.away_team.score = 2.0;
+        for bettor in [&mut home, &mut away, &mut value] {
+            bettor.settle(&game);
+        }
+        assert_eq!((home.coins, home.wins, home.returned), (270.0, 1, 40.0));
+        assert_eq!((away.coins, away.wins, away.returned), (230.0, 0, 0.0));
+        assert_eq!(value.coins, 250.0);
+
+        //a bet only settles once
+        home.settle(&game);
+        assert_eq!(home.coins, 270.0);
+    }
+
+    #[test]
+    fn bets_stop_when_the_coins_run_out() {
+        let game = game();
+        let mut bettor = Bettor::new(Strategy::Favorite, 15.0, MAX_BET);
+        bettor.bet(&game, 0.7);
+        assert_eq!((bettor.coins, bettor.wagered), (0.0, 15.0));
+        bettor.bet(&game, 0.7);
+        assert_eq!(bettor.placed, 1);
+    }
+}
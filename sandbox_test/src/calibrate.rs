@@ -0,0 +1,171 @@
+use std::{collections::BTreeMap, fs};
+
+use crate::{feed::{classify, plays, FeedEvent}, get::{self, world, ChronGameState, Snapshot}};
+use sandbox::{constants::Constants, entities::World, mods::Mod, ruleset::Ruleset, Weather};
+use uuid::Uuid;
+
+//the constants that can be fitted from game logs, in the order they're reported
+pub const CALIBRATED: [&str; 5] = ["debt_hbp", "reverberating", "haunted", "salmon", "mild_pitch"];
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tally {
+    pub opportunities: usize,
+    pub activations: usize,
+}
+
+impl Tally {
+    fn add(&mut self, activated: bool) {
+        self.opportunities += 1;
+        if activated {
+            self.activations += 1;
+        }
+    }
+
+    //every opportunity is a bernoulli trial, so the maximum likelihood estimate is just the ratio
+    //the interval is wilson's 95% score interval, which behaves for the tiny counts we get
+    pub fn fit(&self) -> Option<(f64, f64, f64)> {
+        if self.opportunities == 0 {
+            return None;
+        }
+        let n = self.opportunities as f64;
+        let p = self.activations as f64 / n;
+        let z = 1.96f64;
+        let denominator = 1.0 + z * z / n;
+        let center = (p + z * z / (2.0 * n)) / denominator;
+        let half_width = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+        Some((p, (center - half_width).max(0.0), (center + half_width).min(1.0)))
+    }
+}
+
+pub struct Calibration {
+    pub season: u8,
+    pub tallies: BTreeMap<&'static str, Tally>, //by constant name, one of CALIBRATED
+}
+
+impl Calibration {
+    fn add(&mut self, name: &'static str, activated: bool) {
+        debug_assert!(CALIBRATED.contains(&name), "{} isn't calibrated", name);
+        self.tallies.entry(name).or_default().add(activated);
+    }
+
+    pub fn tally(&self, name: &str) -> Tally {
+        self.tallies.get(name).copied().unwrap_or_default()
+    }
+}
+
+fn has(world: &World, player: Option<Uuid>, m: Mod) -> bool {
+    player.and_then(|id| world.players.get(&id)).map(|p| p.mods.has(m)).unwrap_or(false)
+}
+
+fn batter(state: &ChronGameState) -> Option<Uuid> {
+    if state.topOfInning { state.awayBatter } else { state.homeBatter }
+}
+
+//counts every chance each event had to happen and how often it did
+//mods are taken from the start of the season, so anyone who gained or lost one midseason is miscounted
+pub fn tally(season: u8, days_in_season: usize) -> Option<Calibration> {
+    //only the mods matter here, but a world still needs a ruleset
+    let ruleset = match Ruleset::new(season) {
+        Ok(ruleset) => ruleset,
+        Err(e) => {
+            println!("{}", e);
+            return None;
+        }
+    };
+    let world = world(&Snapshot::season_start(season), ruleset);
+    let mut calibration = Calibration { season, tallies: BTreeMap::new() };
+    for day in 0..days_in_season {
+        let updates = get::game_updates(season, day)?;
+        for (_, game) in plays(&updates) {
+            let mut current_batter: Option<Uuid> = None;
+            let mut last_event = FeedEvent::Other;
+            let mut inning_scores = (0.0, 0.0);
+            let mut salmon_pending = false;
+            for update in game {
+                let state = &update.data;
+                let event = classify(&state.lastUpdate);
+
+                if event.pitch() {
+                    let pitcher = if state.topOfInning { state.homePitcher } else { state.awayPitcher };
+                    let debt = has(&world, pitcher, Mod::DebtU) && !has(&world, current_batter, Mod::Unstable)
+                        || has(&world, pitcher, Mod::RefinancedDebt) && !has(&world, current_batter, Mod::Flickering)
+                        || has(&world, pitcher, Mod::ConsolidatedDebt) && !has(&world, current_batter, Mod::Repeating);
+                    if debt {
+                        calibration.add("debt_hbp", event == FeedEvent::HitByPitch);
+                    }
+                    if has(&world, pitcher, Mod::Mild) {
+                        calibration.add("mild_pitch", event == FeedEvent::MildPitch);
+                    }
+                }
+
+                if let FeedEvent::BatterUp | FeedEvent::Reverberating | FeedEvent::Inhabiting = event {
+                    //the first batter of an inning can't be preceded by a reverberation
+                    if last_event != FeedEvent::InningSwitch && has(&world, current_batter, Mod::Reverberating) {
+                        calibration.add("reverberating", event == FeedEvent::Reverberating);
+                    }
+                    if event == FeedEvent::Inhabiting || event == FeedEvent::BatterUp && has(&world, batter(state), Mod::Haunted) {
+                        calibration.add("haunted", event == FeedEvent::Inhabiting);
+                    }
+                }
+
+                //the salmon only get a chance at the start of an inning where somebody scored
+                if salmon_pending {
+                    calibration.add("salmon", event == FeedEvent::Salmon);
+                    salmon_pending = false;
+                }
+                if event == FeedEvent::InningSwitch && state.topOfInning {
+                    let scores = (state.homeScore, state.awayScore);
+                    if let Some(Weather::Salmon) = state.weather.and_then(Weather::from_id) {
+                        salmon_pending = state.inning > 0 && scores != inning_scores;
+                    }
+                    inning_scores = scores;
+                } else if event == FeedEvent::Salmon {
+                    inning_scores = (state.homeScore, state.awayScore);
+                }
+
+                if batter(state).is_some() {
+                    current_batter = batter(state);
+                }
+                last_event = event;
+            }
+        }
+    }
+    Some(calibration)
+}
+
+fn describe(tally: &Tally) -> String {
+    match tally.fit() {
+        Some((p, low, high)) => format!("{}/{} = {:.5} [{:.5}, {:.5}]", tally.activations, tally.opportunities, p, low, high),
+        None => "no opportunities".to_string()
+    }
+}
+
+//prints each season's fit and the pooled one, and returns the defaults with the pooled fits applied
+pub fn report(calibrations: &[Calibration], base: &Constants) -> Constants {
+    let mut fitted = base.clone();
+    let defaults = base.values();
+    for name in CALIBRATED {
+        let default = defaults.iter().find(|(n, _)| *n == name).unwrap().1;
+        println!("{} (currently {}):", name, default);
+        let mut pooled = Tally::default();
+        for calibration in calibrations {
+            let tally = calibration.tally(name);
+            println!("    season {}: {}", calibration.season + 1, describe(&tally));
+            pooled.opportunities += tally.opportunities;
+            pooled.activations += tally.activations;
+        }
+        println!("    pooled: {}", describe(&pooled));
+        if let Some((p, _, _)) = pooled.fit() {
+            fitted.set(&format!("{} = {}", name, p)).unwrap();
+        }
+    }
+    fitted
+}
+
+pub fn write(constants: &Constants, seasons: &[u8], path: &str) {
+    let seasons: Vec<String> = seasons.iter().map(|s| (s + 1).to_string()).collect();
+    let text = format!("# fitted from seasons {}\n{}", seasons.join(", "), constants.write());
+    if let Err(e) = fs::write(path, text) {
+        println!("file error: {}", e);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::get::ChronGameUpdate;
+use sandbox::events::Event;
+use uuid::Uuid;
+
+//what an update's lastUpdate text says happened
+//only as fine-grained as validation and replay need; everything else is Other
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeedEvent {
+    BatterUp,
+    InningSwitch,
+    GameOver,
+    Ball,
+    Strike,
+    Foul,
+    Steal,
+    CaughtStealing,
+    Hit,
+    HomeRun,
+    Strikeout,
+    Walk,
+    HitByPitch,
+    Out,
+    Incineration,
+    Peanut,
+    Feedback,
+    Reverberating,
+    Inhabiting,
+    Salmon,
+    MildPitch,
+    Other,
+}
+
+impl FeedEvent {
+    pub fn plate_appearance(&self) -> bool {
+        matches!(self, FeedEvent::Hit | FeedEvent::HomeRun | FeedEvent::Strikeout | FeedEvent::Walk | FeedEvent::HitByPitch | FeedEvent::Out)
+    }
+
+    //anything that took a pitch to happen
+    pub fn pitch(&self) -> bool {
+        self.plate_appearance() || matches!(self, FeedEvent::Ball | FeedEvent::Strike | FeedEvent::Foul | FeedEvent::MildPitch)
+    }
+}
+
+//the same categories for a sandbox event
+pub fn from_event(evt: &Event) -> FeedEvent {
+    match evt {
+        Event::BatterUp { .. } => FeedEvent::BatterUp,
+        Event::InningSwitch { .. } => FeedEvent::InningSwitch,
+        Event::GameOver => FeedEvent::GameOver,
+        Event::Ball => FeedEvent::Ball,
+        Event::Strike => FeedEvent::Strike,
+        Event::Foul => FeedEvent::Foul,
+        Event::BaseSteal { .. } => FeedEvent::Steal,
+        Event::CaughtStealing { .. } => FeedEvent::CaughtStealing,
+        Event::BaseHit { .. } => FeedEvent::Hit,
+        Event::HomeRun | Event::MagmaticHomeRun => FeedEvent::HomeRun,
+        Event::Strikeout | Event::CharmStrikeout => FeedEvent::Strikeout,
+        Event::Walk | Event::CharmWalk | Event::InstinctWalk { .. } => FeedEvent::Walk,
+        Event::MildPitch | Event::MildWalk => FeedEvent::MildPitch,
+        Event::HitByPitch { .. } => FeedEvent::HitByPitch,
+        Event::GroundOut { .. } | Event::Flyout { .. } | Event::DoublePlay { .. } | Event::FieldersChoice { .. } => FeedEvent::Out,
+        Event::Incineration { .. } => FeedEvent::Incineration,
+        Event::Peanut { .. } => FeedEvent::Peanut,
+        Event::Feedback { .. } => FeedEvent::Feedback,
+        Event::Reverberating { .. } => FeedEvent::Reverberating,
+        Event::Inhabiting { .. } => FeedEvent::Inhabiting,
+        Event::Salmon { .. } => FeedEvent::Salmon,
+        _ => FeedEvent::Other
+    }
+}
+
+//order matters here: charm and mind trick texts mention both walks and strikeouts,
+//fielder's choice texts contain an "out at", and a mild pitch can also be a walk
+pub fn classify(text: &str) -> FeedEvent {
+    if text.starts_with("Foul Ball") {
+        FeedEvent::Foul
+    } else if text.starts_with("Ball.") {
+        FeedEvent::Ball
+    } else if text.starts_with("Strike,") {
+        FeedEvent::Strike
+    } else if text.contains("batting for the") {
+        FeedEvent::BatterUp
+    } else if text.starts_with("Top of") || text.starts_with("Bottom of") {
+        FeedEvent::InningSwitch
+    } else if text.starts_with("Game over") {
+        FeedEvent::GameOver
+    } else if text.contains("incinerated") {
+        FeedEvent::Incineration
+    } else if text.contains("stray peanut") {
+        FeedEvent::Peanut
+    } else if text.contains("Reality flickers") || text.contains("in the feedback") {
+        FeedEvent::Feedback
+    } else if text.contains("Reverberating") {
+        FeedEvent::Reverberating
+    } else if text.contains("Inhabiting") {
+        FeedEvent::Inhabiting
+    } else if text.contains("Salmon swim upstream") {
+        FeedEvent::Salmon
+    } else if text.contains("Mild pitch") {
+        FeedEvent::MildPitch
+    } else if text.contains("home run") || text.contains("grand slam") {
+        FeedEvent::HomeRun
+    } else if text.contains("hits a Single") || text.contains("hits a Double") || text.contains("hits a Triple") || text.contains("hits a Quadruple") {
+        FeedEvent::Hit
+    } else if text.contains("strikes out") || text.contains("struck out") {
+        FeedEvent::Strikeout
+    } else if text.contains("draws a walk") || text.contains("walks to first") || text.contains("walk") && text.contains("charm") {
+        FeedEvent::Walk
+    } else if text.contains("with a pitch") || text.contains("hit by a pitch") {
+        FeedEvent::HitByPitch
+    } else if text.contains("caught stealing") {
+        FeedEvent::CaughtStealing
+    } else if text.contains("steals") {
+        FeedEvent::Steal
+    } else if text.contains("ground out") || text.contains("flyout") || text.contains("double play") || text.contains("fielder's choice") {
+        FeedEvent::Out
+    } else {
+        FeedEvent::Other
+    }
+}
+
+//updates with a new play, grouped by game and in order
+//chronicler records an update whenever anything changes, so the same play can show up more than once
+pub fn plays(updates: &[ChronGameUpdate]) -> Vec<(Uuid, Vec<&ChronGameUpdate>)> {
+    let mut sorted: Vec<&ChronGameUpdate> = updates.iter().collect();
+    sorted.sort_by(|u1, u2| u1.gameId.cmp(&u2.gameId).then(u1.timestamp.cmp(&u2.timestamp)));
+    let mut games: Vec<(Uuid, Vec<&ChronGameUpdate>)> = Vec::new();
+    for update in sorted {
+        if games.last().map(|(id, _)| *id != update.gameId).unwrap_or(true) {
+            games.push((update.gameId, Vec::new()));
+        }
+        let plays = &mut games.last_mut().unwrap().1;
+        let duplicate = plays.last().map(|last| {
+            match (last.data.playCount, update.data.playCount) {
+                (Some(c1), Some(c2)) => c1 == c2,
+                _ => last.data.lastUpdate == update.data.lastUpdate
+            }
+        }).unwrap_or(false);
+        if !duplicate {
+            plays.push(update);
+        }
+    }
+    games
+}
@@ -1,130 +1,163 @@
 use reqwest::blocking::get;
-use serde::Deserialize;
-use serde_json;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use uuid::Uuid;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use std::str::FromStr;
-use sandbox::{entities::{Player, Team, World}, mods::{Mods, Mod, ModLifetime}, events::Events};
+use sandbox::{entities::{Blood, Coffee, LegendaryItem, Player, PlayerAttr, Stadium, Team, UuidMap, World}, mods::{Mods, ModLifetime}, events::Events, ruleset::Ruleset, Weather};
+use crate::schedule::ScheduleGame;
 
-pub fn world(season: u8) -> World {
-    let mut world = World::new(season);
+pub fn world(snapshot: &Snapshot, ruleset: Ruleset) -> World {
+    let season = snapshot.season;
+    let mut world = World::new(ruleset);
     let divisions = divisions(season).unwrap().convert();
+    let standings = if snapshot.day.is_some() { standings(snapshot) } else { None };
     for &t in divisions.iter() {
-        let team = team(t, season).unwrap().convert();
+        let chron_team = team(t, snapshot).unwrap();
+        if let Some(s) = chron_team.stadium {
+            world.insert_stadium(stadium(s, snapshot).unwrap().convert());
+        }
+        //teams from before rotationSlot was recorded pick up where day % rotation length left off
+        let slot = chron_team.rotationSlot;
+        let mut team = chron_team.convert();
+        if slot.is_none() {
+            team.rotation_slot = snapshot.day.unwrap_or(0);
+        }
+        if let Some(ref st) = standings {
+            team.wins = *st.wins.get(&t).unwrap_or(&0);
+            team.losses = *st.losses.get(&t).unwrap_or(&0);
+        }
         world.insert_team(team);
         //there's got to be a better way
         for p in world.team(t).rotation.clone() {
-            world.insert_player(player(p, season).unwrap().convert());
+            world.insert_player(player(p, snapshot).unwrap().convert());
         }
         for p in world.team(t).lineup.clone() {
-            world.insert_player(player(p, season).unwrap().convert());
+            world.insert_player(player(p, snapshot).unwrap().convert());
         }
         for p in world.team(t).shadows.clone() {
-            world.insert_player(player(p, season).unwrap().convert());
+            world.insert_player(player(p, snapshot).unwrap().convert());
         }
     }
-    for deceased in hall(season).unwrap() {
-        world.insert_player(player(deceased.playerId, season).unwrap().convert());
+    for deceased in hall(snapshot).unwrap() {
+        world.insert_player(player(deceased.playerId, snapshot).unwrap().convert());
         world.hall.push(deceased.playerId);
     }
-    return world;
+    world
 }
 
-//todo: unwrap -> ?, println! -> panic!
-pub fn divisions(season: u8) -> Option<ChronArray<ChronDivision>> { 
-    let timestamp = match season {
-        11 => "2021-03-01T15:00:00Z",
-        _ => todo!(),
-    };
-    let mut result: ChronArray<ChronDivision>;
-    let string = format!("json/s{}divisions.json", season);
-    let path = Path::new(&string);
-    let cached = path.exists();
-    let ignore_cache = false;
-    if cached && !ignore_cache {
-        let file = fs::read(path);
-        if file.is_err() {
-            println!("cache error: {}", file.unwrap_err());
-            return None;
-        }
-        result = serde_json::from_slice(file.unwrap().as_slice()).unwrap();
-    } else {
-        let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=division&at={}", timestamp);
-        let res = get(url);
-        if res.is_err() {
-            println!("request error: {}", res.unwrap_err());
-            return None;
-        }
-        let bytes = res.unwrap().bytes().unwrap();
-        if !Path::new("json").exists() {
-            if fs::create_dir("json").is_err() {
-                println!("directory creation error");
-            }
-        }
-        let data = serde_json::from_slice(&bytes);
-        if data.is_ok() {
-            if fs::write(path, bytes).is_err() {
-                println!("write error");
-            }
-        } //this looks stupid but it has to to compile
-        if data.is_err() {
-            println!("json error: {}", data.unwrap_err());
-            return None;
+//seasons we don't have a known start time for start right before their first game
+fn timestamp(season: u8) -> String {
+    match season {
+        11 => "2021-03-01T15:00:00Z".to_string(),
+        _ => Snapshot::day(season, 0)
+            .unwrap_or_else(|| panic!("couldn't find when season {} started", season))
+            .timestamp,
+    }
+}
+
+//the point in time entities get loaded at
+pub struct Snapshot {
+    pub season: u8,
+    pub day: Option<usize>, //0-indexed, None is the start of the season
+    timestamp: String,
+}
+
+impl Snapshot {
+    pub fn season_start(season: u8) -> Snapshot {
+        Snapshot {
+            season,
+            day: None,
+            timestamp: timestamp(season),
         }
-        result = data.unwrap();
     }
-    if season == 11 {
-        result.items.retain(|item| item.validTo.is_some());
+
+    //right before the first game of the day starts
+    pub fn day(season: u8, day: usize) -> Option<Snapshot> {
+        let timestamp = games(season)?
+            .data
+            .into_iter()
+            .filter(|g| g.data.day == day)
+            .filter_map(|g| g.startTime)
+            .min()?;
+        Some(Snapshot {
+            season,
+            day: Some(day),
+            timestamp,
+        })
     }
 
-    return Some(result);
+    fn cache(&self, name: &str) -> String {
+        match self.day {
+            Some(day) => format!("json/s{}d{}{}.json", self.season, day, name),
+            None => format!("json/s{}{}.json", self.season, name),
+        }
+    }
 }
 
-pub fn hall(season: u8) -> Option<ChronHall> {
-    let timestamp = match season {
-        11 => "2021-03-01T15:00:00Z",
-        _ => todo!(),
-    };
-    let mut result: ChronArray<ChronHall>;
-    let string = format!("json/s{}hall.json", season);
-    let path = Path::new(&string);
+//reads the cached file at `cache` if there is one,
+//otherwise requests `url` and caches the response
+fn fetch<D: DeserializeOwned>(url: String, cache: String, ignore_cache: bool) -> Option<D> {
+    let path = Path::new(&cache);
     let cached = path.exists();
-    let ignore_cache = false;
     if cached && !ignore_cache {
-        let file = fs::read(path);
-        if file.is_err() {
-            println!("cache error: {}", file.unwrap_err());
-            return None;
-        }
-        result = serde_json::from_slice(file.unwrap().as_slice()).unwrap();
-    } else {
-        let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=tributes&at={}", timestamp);
-        let res = get(url);
-        if res.is_err() {
-            println!("request error: {}", res.unwrap_err());
+        let file = match fs::read(path) {
+            Ok(file) => file,
+            Err(e) => {
+                println!("cache error: {}", e);
+                return None;
+            }
+        };
+        return match serde_json::from_slice(file.as_slice()) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                println!("json error: {}", e);
+                None
+            }
+        };
+    }
+    let bytes = match get(url).and_then(|res| res.bytes()) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            println!("request error: {}", e);
             return None;
         }
-        let bytes = res.unwrap().bytes().unwrap();
-        if !Path::new("json").exists() {
-            if fs::create_dir("json").is_err() {
-                println!("directory creation error");
-            }
+    };
+    if !Path::new("json").exists() {
+        if let Err(e) = fs::create_dir("json") {
+            println!("directory creation error: {}", e);
         }
-        let data = serde_json::from_slice(&bytes);
-        if data.is_ok() {
-            if fs::write(path, bytes).is_err() {
-                println!("write error");
-            }
-        } //this looks stupid but it has to to compile
-        if data.is_err() {
-            println!("json error: {}", data.unwrap_err());
+    }
+    let data = match serde_json::from_slice(&bytes) {
+        Ok(data) => data,
+        Err(e) => {
+            println!("json error: {}", e);
             return None;
         }
-        result = data.unwrap();
+    };
+    if let Err(e) = fs::write(path, bytes) {
+        println!("write error: {}", e);
+    }
+    Some(data)
+}
+
+//todo: unwrap -> ?, println! -> panic!
+pub fn divisions(season: u8) -> Option<ChronArray<ChronDivision>> { 
+    let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=division&at={}", timestamp(season));
+    let mut result: ChronArray<ChronDivision> = fetch(url, format!("json/s{}divisions.json", season), false)?;
+    if season == 11 {
+        result.items.retain(|item| item.validTo.is_some());
     }
 
-    return Some(result.items[0].data.clone());
+    Some(result)
+}
+
+pub fn hall(snapshot: &Snapshot) -> Option<ChronHall> {
+    let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=tributes&at={}", snapshot.timestamp);
+    let result: ChronArray<ChronHall> = fetch(url, snapshot.cache("hall"), false)?;
+
+    Some(result.items[0].data.clone())
 }
 
 pub fn tiebreakers(season: u8) -> Option<Vec<Uuid>> {
@@ -136,139 +169,121 @@ pub fn tiebreakers(season: u8) -> Option<Vec<Uuid>> {
         11 => "2021-03-01T15:00:00Z",
         _ => todo!(),
     };
-    let mut result: ChronArray<ChronFate>;
-    let string = format!("json/s{}fate.json", season);
-    let path = Path::new(&string);
-    let cached = path.exists();
-    let ignore_cache = true;
-    if cached && !ignore_cache {
-        let file = fs::read(path);
-        if file.is_err() {
-            println!("cache error: {}", file.unwrap_err());
-            return None;
-        }
-        result = serde_json::from_slice(file.unwrap().as_slice()).unwrap();
-    } else {
-        //have to do this because of non-standard data in the entities API
-        let url = format!("https://api.sibr.dev/chronicler/v2/versions?type=tiebreakers&before={}&after={}", timestamp_before, timestamp);
-        let res = get(url);
-        if res.is_err() {
-            println!("request error: {}", res.unwrap_err());
-            return None;
-        }
-        let bytes = res.unwrap().bytes().unwrap();
-        if !Path::new("json").exists() {
-            if fs::create_dir("json").is_err() {
-                println!("directory creation error");
-            }
-        }
-        let data = serde_json::from_slice(&bytes);
-        if data.is_ok() {
-            if fs::write(path, bytes).is_err() {
-                println!("write error");
-            }
-        } //this looks stupid but it has to to compile
-        if data.is_err() {
-            println!("json error: {}", data.unwrap_err());
-            return None;
-        }
-        result = data.unwrap();
-    }
+    //have to do this because of non-standard data in the entities API
+    let url = format!("https://api.sibr.dev/chronicler/v2/versions?type=tiebreakers&before={}&after={}", timestamp_before, timestamp);
+    let mut result: ChronArray<ChronFate> = fetch(url, format!("json/s{}fate.json", season), true)?;
     if season == 11 {
         result.items.retain(|item| item.validTo.is_some());
     }
 
-    return Some(result.items[0].data.order.clone());
+    Some(result.items[0].data.order.clone())
 }
 
-pub fn team(id: Uuid, season: u8) -> Option<ChronTeam> {
-    let timestamp = match season {
-        11 => "2021-03-01T15:00:00Z",
-        _ => todo!(),
-    };
-    let mut result: ChronArray<ChronTeam>;
-    let string = format!("json/s{}teams_{}.json", season, id);
-    let path = Path::new(&string);
-    let cached = path.exists();
-    let ignore_cache = false;
-    if cached && !ignore_cache {
-        let file = fs::read(path);
-        if file.is_err() {
-            println!("cache error: {}", file.unwrap_err());
-            return None;
-        }
-        result = serde_json::from_slice(file.unwrap().as_slice()).unwrap();
-    } else {
-        let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=team&id={}&at={}", id, timestamp);
-        let res = get(url);
-        if res.is_err() {
-            println!("request error: {}", res.unwrap_err());
-            return None;
-        }
-        let bytes = res.unwrap().bytes().unwrap();
-        if !Path::new("json").exists() {
-            if fs::create_dir("json").is_err() {
-                println!("directory creation error");
+pub fn team(id: Uuid, snapshot: &Snapshot) -> Option<ChronTeam> {
+    let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=team&id={}&at={}", id, snapshot.timestamp);
+    let result: ChronArray<ChronTeam> = fetch(url, snapshot.cache(&format!("teams_{}", id)), false)?;
+    Some(result.items[0].data.clone())
+}
+
+pub fn player(id: Uuid, snapshot: &Snapshot) -> Option<ChronPlayer> {
+    let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=player&id={}&at={}", id, snapshot.timestamp);
+    let result: ChronArray<ChronPlayer> = fetch(url, snapshot.cache(&format!("players_{}", id)), false)?;
+    Some(result.items[0].data.clone())
+}
+
+pub fn stadium(id: Uuid, snapshot: &Snapshot) -> Option<ChronStadium> {
+    let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=stadium&id={}&at={}", id, snapshot.timestamp);
+    let result: ChronArray<ChronStadium> = fetch(url, snapshot.cache(&format!("stadiums_{}", id)), false)?;
+    Some(result.items[0].data.clone())
+}
+
+pub fn standings(snapshot: &Snapshot) -> Option<ChronStandings> {
+    //every season has its own standings object
+    let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=season&at={}", snapshot.timestamp);
+    let seasons: ChronArray<ChronSeason> = fetch(url, snapshot.cache("seasons"), false)?;
+    let standings_id = seasons.items
+        .iter()
+        .find(|item| item.data.seasonNumber == snapshot.season)?
+        .data
+        .standings;
+    let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=standings&id={}&at={}", standings_id, snapshot.timestamp);
+    let result: ChronArray<ChronStandings> = fetch(url, snapshot.cache("standings"), false)?;
+    Some(result.items[0].data.clone())
+}
+
+//every game of the season, including the ones that haven't been played at the time
+pub fn games(season: u8) -> Option<ChronGames> {
+    let url = format!("https://api.sibr.dev/chronicler/v1/games?season={}", season);
+    fetch(url, format!("json/s{}games.json", season), false)
+}
+
+//real schedule and weathers from `day` to the end of the regular season.
+//a weather sandbox doesn't know is an error rather than a rolled one, the point is the real weathers
+pub fn schedule(season: u8, day: usize, days: usize) -> Option<Vec<ScheduleGame>> {
+    let mut games: Vec<ChronGameData> = games(season)?
+        .data
+        .into_iter()
+        .map(|g| g.data)
+        .filter(|g| g.day >= day && g.day < days)
+        .collect();
+    games.sort_by(|g1, g2| g1.day.cmp(&g2.day).then(g1.id.cmp(&g2.id)));
+    let mut schedule = Vec::new();
+    for (i, game) in games.iter().enumerate() {
+        let order = i - games.iter().position(|g| g.day == game.day).unwrap();
+        let weather = match game.weather.and_then(Weather::from_id) {
+            Some(weather) => weather,
+            None => {
+                println!("Couldn't parse weather {:?} for game {} on day {}", game.weather, game.id, game.day);
+                return None;
             }
+        };
+        schedule.push(ScheduleGame::new(game.day, order, game.homeTeam, game.awayTeam, Some(weather)));
+    }
+    Some(schedule)
+}
+
+//every recorded update of every game on a day, in order
+//these are big, so each page gets its own cache file
+pub fn game_updates(season: u8, day: usize) -> Option<Vec<ChronGameUpdate>> {
+    let mut updates = Vec::new();
+    let mut page: Option<String> = None;
+    let mut page_number = 0;
+    loop {
+        let mut url = format!("https://api.sibr.dev/chronicler/v1/games/updates?season={}&day={}&count=1000&order=asc", season, day);
+        if let Some(ref p) = page {
+            url.push_str(&format!("&page={}", p));
         }
-        let data = serde_json::from_slice(&bytes);
-        if data.is_ok() {
-            if fs::write(path, bytes).is_err() {
-                println!("write error");
-            }
-        } //this looks stupid but it has to to compile
-        if data.is_err() {
-            println!("json error: {}", data.unwrap_err());
-            return None;
+        let result: ChronUpdates = fetch(url, format!("json/s{}d{}updates_{}.json", season, day, page_number), false)?;
+        let done = result.data.is_empty() || result.nextPage.is_none();
+        updates.extend(result.data);
+        if done {
+            break;
         }
-        result = data.unwrap();
+        page = result.nextPage;
+        page_number += 1;
     }
-    return Some(result.items[0].data.clone());
+    Some(updates)
 }
 
-pub fn player(id: Uuid, season: u8) -> Option<ChronPlayer> {
-    let timestamp = match season {
-        11 => "2021-03-01T15:00:00Z",
-        _ => todo!(),
-    };
-    let mut result: ChronArray<ChronPlayer>;
-    let string = format!("json/s{}players_{}.json", season, id);
-    let path = Path::new(&string);
-    let cached = path.exists();
-    let ignore_cache = false;
-    if cached && !ignore_cache {
-        let file = fs::read(path);
-        if file.is_err() {
-            println!("cache error: {}", file.unwrap_err());
-            return None;
-        }
-        result = serde_json::from_slice(file.unwrap().as_slice()).unwrap();
-    } else {
-        let url = format!("https://api.sibr.dev/chronicler/v2/entities?type=player&id={}&at={}", id, timestamp);
-        let res = get(url);
-        if res.is_err() {
-            println!("request error: {}", res.unwrap_err());
+//game updates from a local file, either a bare array or a page from the updates endpoint
+pub fn game_updates_file(path: &str) -> Option<Vec<ChronGameUpdate>> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            println!("file error: {}", e);
             return None;
         }
-        let bytes = res.unwrap().bytes().unwrap();
-        if !Path::new("json").exists() {
-            if fs::create_dir("json").is_err() {
-                println!("directory creation error");
-            }
-        }
-        let data = serde_json::from_slice(&bytes);
-        if data.is_ok() {
-            if fs::write(path, bytes).is_err() {
-                println!("write error");
-            }
-        } //this looks stupid but it has to to compile
-        if data.is_err() {
-            println!("json error: {}", data.unwrap_err());
-            return None;
+    };
+    if let Ok(updates) = serde_json::from_slice::<Vec<ChronGameUpdate>>(&bytes) {
+        return Some(updates);
+    }
+    match serde_json::from_slice::<ChronUpdates>(&bytes) {
+        Ok(result) => Some(result.data),
+        Err(e) => {
+            println!("json error: {}", e);
+            None
         }
-        result = data.unwrap();
     }
-    return Some(result.items[0].data.clone());
 }
 
 #[derive(Deserialize, Debug)]
@@ -289,14 +304,12 @@ impl ChronArray<ChronDivision> {
 #[derive(Deserialize, Debug)]
 #[allow(non_snake_case)]
 pub struct ChronItem<D> {
-    validFrom: String, //todo: these are timestamps
     validTo: Option<String>,
     pub data: D
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct ChronDivision {
-    pub id: Uuid,
     pub name: String,
     pub teams: Vec<Uuid>
 }
@@ -306,18 +319,99 @@ pub type ChronHall = Vec<ChronTribute>;
 #[derive(Deserialize, Debug, Clone)]
 #[allow(non_snake_case)]
 pub struct ChronTribute {
-    pub peanuts: u64,
     pub playerId: Uuid,
 }
 
 #[derive(Deserialize, Debug, Clone)]
-pub struct ChronFate {
+#[allow(non_snake_case)]
+pub struct ChronSeason {
+    pub seasonNumber: u8,
+    pub standings: Uuid,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChronStandings {
+    pub wins: BTreeMap<Uuid, i16>,
+    pub losses: BTreeMap<Uuid, i16>,
+}
+
+//the v1 games endpoint doesn't use ChronArray
+#[derive(Deserialize, Debug)]
+pub struct ChronGames {
+    pub data: Vec<ChronGame>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+pub struct ChronGame {
+    pub startTime: Option<String>,
+    pub data: ChronGameData,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+pub struct ChronGameData {
     pub id: Uuid,
-    pub order: Vec<Uuid>
+    pub day: usize, //0-indexed
+    pub weather: Option<u8>,
+    pub homeTeam: Uuid,
+    pub awayTeam: Uuid,
+    pub homeScore: f64,
+    pub awayScore: f64,
+    pub gameComplete: bool,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(non_snake_case)]
+pub struct ChronUpdates {
+    pub nextPage: Option<String>,
+    pub data: Vec<ChronGameUpdate>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+pub struct ChronGameUpdate {
+    pub gameId: Uuid,
+    pub timestamp: String,
+    pub data: ChronGameState,
 }
 
+//a game as it was at one update
 #[derive(Deserialize, Debug, Clone)]
 #[allow(non_snake_case)]
+pub struct ChronGameState {
+    pub day: usize,
+    pub season: u8,
+    pub weather: Option<u8>,
+    pub homeTeam: Uuid,
+    pub awayTeam: Uuid,
+    pub homeScore: f64,
+    pub awayScore: f64,
+    pub inning: i16, //0-indexed, unlike Game
+    pub topOfInning: bool,
+    pub halfInningOuts: i16,
+    pub atBatBalls: i16,
+    pub atBatStrikes: i16,
+    #[serde(default)]
+    pub baseRunners: Vec<Uuid>,
+    #[serde(default)]
+    pub basesOccupied: Vec<u8>,
+    pub homeBatter: Option<Uuid>,
+    pub awayBatter: Option<Uuid>,
+    pub homePitcher: Option<Uuid>,
+    pub awayPitcher: Option<Uuid>,
+    #[serde(default)]
+    pub lastUpdate: String,
+    pub playCount: Option<i64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChronFate {
+    pub order: Vec<Uuid>
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[allow(non_snake_case)]
 pub struct ChronTeam {
     pub id: Uuid,
     pub fullName: String,
@@ -327,8 +421,12 @@ pub struct ChronTeam {
     pub rotation: Vec<Uuid>,
     pub bench: Vec<Uuid>,
     pub bullpen: Vec<Uuid>,
+    #[serde(default)]
+    pub rotationSlot: Option<usize>,
 
     pub stadium: Option<Uuid>,
+    #[serde(default)]
+    pub shameRuns: f64,
 
     pub permAttr: Vec<String>,
     pub seasAttr: Vec<String>,
@@ -345,48 +443,107 @@ impl ChronTeam {
 
             lineup: self.lineup,
             rotation: self.rotation,
+            bench_size: self.bench.len(),
             shadows: [self.bench, self.bullpen].concat(),
+            rotation_slot: self.rotationSlot.unwrap_or(0),
 
             wins: 0,
             losses: 0,
             postseason_wins: 0,
             postseason_losses: 0,
+            shames: 0,
+            shamings: 0,
+            shame_runs: self.shameRuns,
             partying: false,
             fate: 0,
 
-            mods: modconvert(&[self.permAttr, self.seasAttr, self.weekAttr, self.gameAttr])
+            mods: modconvert(&[self.permAttr, self.seasAttr, self.weekAttr, self.gameAttr]),
+            stadium: self.stadium,
+        }
+    }
+
+    pub fn export(team: &Team) -> ChronTeam {
+        let [perm, seas, week, game] = attrconvert(&team.mods);
+        ChronTeam {
+            id: team.id,
+            fullName: team.name.clone(),
+            emoji: team.emoji.clone(),
+
+            lineup: team.lineup.clone(),
+            rotation: team.rotation.clone(),
+            bench: team.shadows[..team.bench_size].to_vec(),
+            bullpen: team.shadows[team.bench_size..].to_vec(),
+            rotationSlot: Some(team.rotation_slot),
+
+            stadium: team.stadium,
+            shameRuns: team.shame_runs,
+
+            permAttr: perm,
+            seasAttr: seas,
+            weekAttr: week,
+            gameAttr: game,
         }
     }
 }
 
+const LIFETIMES: [ModLifetime; 4] = [ModLifetime::Permanent, ModLifetime::Season, ModLifetime::Week, ModLifetime::Game];
+
 fn modconvert(mods: &[Vec<String>]) -> Mods {
     let mut smods = Mods::new();
     for i in 0..4 {
         for m in mods[i].iter() {
-            let ignore = ["ALTERNATE", "RECEIVER", "RETURNED", "FIRST_BORN", "NON_IDOLIZED", "DOUBLE_PAYOUTS", "SUPER_IDOL", "CREDIT_TO_THE_TEAM"];
-            let lifetime = match i {
-                0 => ModLifetime::Permanent,
-                1 => ModLifetime::Season,
-                2 => ModLifetime::Week,
-                3 => ModLifetime::Game,
-                _ => {
-                    panic!("Invalid parsed lifetime, somehow")
-                }
-            };
-            if !ignore.contains(&m.as_str()) {
-                let smod = Mod::from_str(m.as_str());
-                if smod.is_err() {
-                    println!("Couldn't parse mod {}", m);
-                    continue;
-                }
-                smods.add(smod.unwrap(), lifetime);
-            }
+            smods.add_attr(m.as_str(), LIFETIMES[i]);
         }
     }
     smods
 }
 
-#[derive(Deserialize, Debug, Clone)]
+//inverse of modconvert; legendary item mods come from the item so they're left out
+fn attrconvert(mods: &Mods) -> [Vec<String>; 4] {
+    LIFETIMES.map(|lifetime| mods.attrs(lifetime))
+}
+
+//in id order, so reports and exports come out the same every time
+fn sorted<V>(map: &UuidMap<V>) -> impl Iterator<Item = &V> {
+    let mut entries: Vec<(&Uuid, &V)> = map.iter().collect();
+    entries.sort_by_key(|&(id, _)| *id);
+    entries.into_iter().map(|(_, v)| v)
+}
+
+//unrecognised attribute -> names of the players and teams that have it
+pub fn unknown_mods(world: &World) -> BTreeMap<String, Vec<String>> {
+    let mut report: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for team in sorted(&world.teams) {
+        for m in team.mods.unknown() {
+            report.entry(m.to_string()).or_default().push(team.name.clone());
+        }
+    }
+    for player in sorted(&world.players) {
+        for m in player.mods.unknown() {
+            report.entry(m.to_string()).or_default().push(player.name.clone());
+        }
+    }
+    report
+}
+
+#[derive(Serialize, Debug)]
+pub struct ChronExport {
+    pub teams: Vec<ChronTeam>,
+    pub players: Vec<ChronPlayer>,
+}
+
+pub fn export(world: &World, path: &str) {
+    let export = ChronExport {
+        teams: sorted(&world.teams).map(ChronTeam::export).collect(),
+        players: sorted(&world.players).map(|p| ChronPlayer::export(p, world.hall.contains(&p.id))).collect(),
+    };
+    let json = serde_json::to_vec_pretty(&export).unwrap();
+    if fs::write(path, json).is_err() {
+        println!("write error");
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[allow(non_snake_case)]
 pub struct ChronPlayer {
     pub id: Uuid,
@@ -425,6 +582,17 @@ pub struct ChronPlayer {
     pub pressurization: f64,
     pub cinnamon: Option<f64>, //lol
 
+    pub soul: Option<u8>,
+    pub peanutAllergy: Option<bool>,
+    pub fate: Option<u8>,
+    pub ritual: Option<String>,
+    pub blood: Option<usize>,
+    pub coffee: Option<usize>,
+
+    //empty strings if there's no item
+    pub bat: Option<String>,
+    pub armor: Option<String>,
+
     pub permAttr: Vec<String>,
     pub seasAttr: Vec<String>,
     pub weekAttr: Vec<String>,
@@ -433,47 +601,250 @@ pub struct ChronPlayer {
 
 impl ChronPlayer {
     pub fn convert(self) -> Player {
-        Player {
+        //the sim only supports one item per player. most items aren't legendary and do nothing here,
+        //and if the bat and the armor both are, the bat wins
+        let item = [&self.bat, &self.armor]
+            .into_iter()
+            .flatten()
+            .find_map(|item| LegendaryItem::from_str(item).ok());
+        let mut player = Player {
             id: self.id,
             name: self.name,
             mods: modconvert(&[self.permAttr, self.seasAttr, self.weekAttr, self.gameAttr]),
             legendary_item: None,
+            bat: self.bat,
+            armor: self.armor,
             team: self.leagueTeamId,
 
             feed: Events::new(),
             swept_on: None,
             scattered_letters: 0,
 
-            buoyancy: self.buoyancy,
-            divinity: self.divinity,
-            martyrdom: self.martyrdom,
-            moxie: self.moxie,
-            musclitude: self.musclitude,
-            patheticism: self.patheticism,
-            thwackability: self.thwackability,
-            tragicness: self.tragicness,
-            
-            coldness: self.coldness,
-            overpowerment: self.overpowerment,
-            ruthlessness: self.ruthlessness,
-            shakespearianism: self.shakespearianism,
-            suppression: self.suppression,
-            unthwackability: self.unthwackability,
-
-            base_thirst: self.baseThirst,
-            continuation: self.continuation,
-            ground_friction: self.groundFriction,
-            indulgence: self.indulgence,
-            laserlikeness: self.laserlikeness,
-
-            anticapitalism: self.anticapitalism,
-            chasiness: self.chasiness,
-            omniscience: self.omniscience,
-            tenaciousness: self.tenaciousness,
-            watchfulness: self.watchfulness,
-
-            pressurization: self.pressurization,
-            cinnamon: self.cinnamon.unwrap_or(0.0),
+            //in PlayerAttr order
+            stats: [
+                self.buoyancy,
+                self.divinity,
+                self.martyrdom,
+                self.moxie,
+                self.musclitude,
+                self.patheticism,
+                self.thwackability,
+                self.tragicness,
+
+                self.coldness,
+                self.overpowerment,
+                self.ruthlessness,
+                self.shakespearianism,
+                self.suppression,
+                self.unthwackability,
+
+                self.baseThirst,
+                self.continuation,
+                self.groundFriction,
+                self.indulgence,
+                self.laserlikeness,
+
+                self.anticapitalism,
+                self.chasiness,
+                self.omniscience,
+                self.tenaciousness,
+                self.watchfulness,
+
+                self.pressurization,
+                self.cinnamon.unwrap_or(0.0),
+            ],
+
+            soul: self.soul.unwrap_or(0),
+            peanut_allergy: self.peanutAllergy,
+            fate: self.fate.unwrap_or(0),
+            ritual: self.ritual.unwrap_or_default(),
+            blood: self.blood.and_then(|blood| Blood::ALL.get(blood).copied()),
+            coffee: self.coffee.and_then(|coffee| Coffee::ALL.get(coffee).copied()),
+        };
+        if let Some(item) = item {
+            player.add_legendary_item(item);
         }
+        player
+    }
+
+    pub fn export(player: &Player, deceased: bool) -> ChronPlayer {
+        let [perm, seas, week, game] = attrconvert(&player.mods);
+        //items go back out under the ids they came in with. one the sim handed out itself goes in the bat slot
+        let imported = [&player.bat, &player.armor].into_iter().flatten().any(|item| LegendaryItem::from_str(item).is_ok());
+        let bat = match &player.legendary_item {
+            Some(item) if !imported => Some(<&'static str>::from(item).to_string()),
+            _ => player.bat.clone(),
+        };
+        ChronPlayer {
+            id: player.id,
+            name: player.name.clone(),
+            leagueTeamId: player.team,
+            deceased,
+
+            buoyancy: player.get(PlayerAttr::Buoyancy),
+            divinity: player.get(PlayerAttr::Divinity),
+            martyrdom: player.get(PlayerAttr::Martyrdom),
+            moxie: player.get(PlayerAttr::Moxie),
+            musclitude: player.get(PlayerAttr::Musclitude),
+            patheticism: player.get(PlayerAttr::Patheticism),
+            thwackability: player.get(PlayerAttr::Thwackability),
+            tragicness: player.get(PlayerAttr::Tragicness),
+
+            coldness: player.get(PlayerAttr::Coldness),
+            overpowerment: player.get(PlayerAttr::Overpowerment),
+            ruthlessness: player.get(PlayerAttr::Ruthlessness),
+            shakespearianism: player.get(PlayerAttr::Shakespearianism),
+            suppression: player.get(PlayerAttr::Suppression),
+            unthwackability: player.get(PlayerAttr::Unthwackability),
+
+            baseThirst: player.get(PlayerAttr::BaseThirst),
+            continuation: player.get(PlayerAttr::Continuation),
+            groundFriction: player.get(PlayerAttr::GroundFriction),
+            indulgence: player.get(PlayerAttr::Indulgence),
+            laserlikeness: player.get(PlayerAttr::Laserlikeness),
+
+            anticapitalism: player.get(PlayerAttr::Anticapitalism),
+            chasiness: player.get(PlayerAttr::Chasiness),
+            omniscience: player.get(PlayerAttr::Omniscience),
+            tenaciousness: player.get(PlayerAttr::Tenaciousness),
+            watchfulness: player.get(PlayerAttr::Watchfulness),
+
+            pressurization: player.get(PlayerAttr::Pressurization),
+            cinnamon: Some(player.get(PlayerAttr::Cinnamon)),
+
+            soul: Some(player.soul),
+            peanutAllergy: player.peanut_allergy,
+            fate: Some(player.fate),
+            ritual: Some(player.ritual.clone()),
+            blood: player.blood.map(|blood| Blood::ALL.iter().position(|&b| b == blood).unwrap()),
+            coffee: player.coffee.map(|coffee| Coffee::ALL.iter().position(|&c| c == coffee).unwrap()),
+
+            bat,
+            armor: player.armor.clone(),
+
+            permAttr: perm,
+            seasAttr: seas,
+            weekAttr: week,
+            gameAttr: game,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+pub struct ChronStadium {
+    pub id: Uuid,
+    pub teamId: Option<Uuid>,
+    pub name: String,
+    pub nickname: String,
+
+    pub mysticism: f64,
+    pub viscosity: f64,
+    pub elongation: f64,
+    pub filthiness: f64,
+    pub obtuseness: f64,
+    pub forwardness: f64,
+    pub grandiosity: f64,
+    pub ominousness: f64,
+    pub fortification: f64,
+    pub inconvenience: f64,
+    pub hype: f64,
+}
+
+impl ChronStadium {
+    pub fn convert(self) -> Stadium {
+        Stadium {
+            id: self.id,
+            team: self.teamId,
+
+            name: self.name,
+            nickname: self.nickname,
+
+            mysticism: self.mysticism,
+            viscosity: self.viscosity,
+            elongation: self.elongation,
+            filthiness: self.filthiness,
+            obtuseness: self.obtuseness,
+            forwardness: self.forwardness,
+            grandiosity: self.grandiosity,
+            ominousness: self.ominousness,
+            fortification: self.fortification,
+            inconvenience: self.inconvenience,
+            hype: self.hype,
+        }
+    }
+}
+
+//sandbox_test is a binary, so the conversions can only be tested from in here
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn chron_player(bat: &str, armor: &str) -> Value {
+        let mut chron: Value = serde_json::from_str(r#"{
+            "id": "00000000-0000-0000-0000-000000000001",
+            "name": "Test Player",
+            "leagueTeamId": "00000000-0000-0000-0000-000000000002",
+            "deceased": false,
+            "buoyancy": 0.1, "divinity": 0.2, "martyrdom": 0.3, "moxie": 0.4,
+            "musclitude": 0.5, "patheticism": 0.6, "thwackability": 0.7, "tragicness": 0.8,
+            "coldness": 0.9, "overpowerment": 1.0, "ruthlessness": 1.1, "shakespearianism": 1.2,
+            "suppression": 1.3, "unthwackability": 1.4,
+            "baseThirst": 1.5, "continuation": 1.6, "groundFriction": 1.7, "indulgence": 1.8, "laserlikeness": 1.9,
+            "anticapitalism": 2.0, "chasiness": 2.1, "omniscience": 2.2, "tenaciousness": 2.3, "watchfulness": 2.4,
+            "pressurization": 2.5, "cinnamon": 2.6,
+            "soul": 7, "peanutAllergy": true, "fate": 42, "ritual": "Counting", "blood": 2, "coffee": 1,
+            "permAttr": ["FIREPROOF"],
+            "seasAttr": ["NOT_A_REAL_MOD"],
+            "weekAttr": [],
+            "gameAttr": []
+        }"#).unwrap();
+        chron["bat"] = json!(bat);
+        chron["armor"] = json!(armor);
+        chron
+    }
+
+    fn round_trip_player(chron: Value) -> Value {
+        let player: ChronPlayer = serde_json::from_value(chron).unwrap();
+        serde_json::to_value(ChronPlayer::export(&player.convert(), false)).unwrap()
+    }
+
+    #[test]
+    fn players_export_the_way_they_import() {
+        //a legendary armor item under its short name, next to a bat that isn't legendary
+        let chron = chron_player("NOT_LEGENDARY", "IFFEY_JR");
+        let player = serde_json::from_value::<ChronPlayer>(chron.clone()).unwrap().convert();
+        assert!(matches!(player.legendary_item, Some(LegendaryItem::TheIffeyJr)));
+        assert_eq!(round_trip_player(chron.clone()), chron);
+        //both legendary, the bat is the one the sim uses
+        let chron = chron_player("ARM_CANNON", "NIGHT_VISION_GOGGLES");
+        let player = serde_json::from_value::<ChronPlayer>(chron.clone()).unwrap().convert();
+        assert!(matches!(player.legendary_item, Some(LegendaryItem::LiteralArmCannon)));
+        assert_eq!(round_trip_player(chron.clone()), chron);
+        let chron = chron_player("", "");
+        assert_eq!(round_trip_player(chron.clone()), chron);
+    }
+
+    #[test]
+    fn teams_export_the_way_they_import() {
+        let chron = json!({
+            "id": "00000000-0000-0000-0000-000000000002",
+            "fullName": "Test Team",
+            "emoji": "0x1F3C8",
+            "lineup": ["00000000-0000-0000-0000-000000000010", "00000000-0000-0000-0000-000000000011"],
+            "rotation": ["00000000-0000-0000-0000-000000000020"],
+            "bench": ["00000000-0000-0000-0000-000000000030", "00000000-0000-0000-0000-000000000031"],
+            "bullpen": ["00000000-0000-0000-0000-000000000040"],
+            "rotationSlot": 3,
+            "stadium": null,
+            "shameRuns": 1.5,
+            "permAttr": ["HOME_FIELD"],
+            "seasAttr": [],
+            "weekAttr": ["NOT_A_REAL_MOD"],
+            "gameAttr": [],
+        });
+        let team: ChronTeam = serde_json::from_value(chron.clone()).unwrap();
+        assert_eq!(serde_json::to_value(ChronTeam::export(&team.convert())).unwrap(), chron);
     }
 }
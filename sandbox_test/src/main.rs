@@ -1,13 +1,15 @@
 use crate::{
     schedule::{generate_games, generate_schedule},
-    get::{world, divisions, tiebreakers}
+    get::{world, divisions, tiebreakers, unknown_mods, export, Snapshot}
 };
 use sandbox::{
-    entities::{LegendaryItem, NameGen, World},
+    entities::World,
     events::Event,
+    constants::{Constants, CONSTANTS_VERSION},
     rng::Rng,
+    ruleset::Ruleset,
     sim::Sim,
-    mods::{Mod, ModLifetime},
+    ratings::{Ratings, Stars},
     Game, Weather
 };
 use uuid::Uuid;
@@ -16,6 +18,11 @@ use clap::Parser;
 mod schedule;
 mod postseason;
 mod get;
+mod feed;
+mod validate;
+mod replay;
+mod calibrate;
+mod betting;
 
 #[derive(Debug, Parser)]
 struct Args {
@@ -34,7 +41,85 @@ struct Args {
     #[arg(long, action)]
     seasonmode: bool,
     #[arg(long, default_value_t=1)]
-    loops: usize
+    loops: usize,
+    #[arg(long)]
+    export: Option<String>,
+    #[arg(long)]
+    day: Option<usize>,
+    #[arg(long, action)]
+    validate: bool,
+    #[arg(long)]
+    replay: Option<String>,
+    #[arg(long)]
+    rolls: Option<String>,
+    #[arg(long)]
+    game: Option<Uuid>,
+    #[arg(long)]
+    constants: Option<String>,
+    #[arg(long)]
+    constant: Vec<String>,
+    #[arg(long, value_delimiter = ',')]
+    calibrate: Vec<u8>,
+    #[arg(long)]
+    calibrateout: Option<String>,
+    #[arg(long, action)]
+    winprob: bool,
+    #[arg(long, action)]
+    betting: bool,
+    #[arg(long, action)]
+    roster: bool
+}
+
+//the defaults depend on the season, so this goes on top of them
+fn load_constants(constants: &mut Constants, args: &Args) {
+    if let Some(path) = &args.constants {
+        let text = std::fs::read_to_string(path).unwrap_or_else(|e| panic!("couldn't read constants from {}: {}", path, e));
+        constants.read(&text).unwrap_or_else(|e| panic!("{}: {}", path, e));
+        println!("Using constants version {} from {}", CONSTANTS_VERSION, path);
+    }
+    for line in args.constant.iter() {
+        constants.set(line).unwrap_or_else(|e| panic!("--constant {}", e));
+    }
+}
+
+fn print_roster(world: &World, id: Uuid, day: usize) {
+    let team = world.team(id);
+    println!("{} {}: {}", team.emoji, team.name, team.ratings(world, |player| Ratings::new(player, &world.ruleset)));
+    println!("    with items and vibes on day {}: {}", day + 1, team.ratings(world, |player| Ratings::with_vibes(player, &world.ruleset, day)));
+    for &id in team.lineup.iter() {
+        let player = world.player(id);
+        let ratings = Ratings::new(player, &world.ruleset);
+        println!("    {}: batting {}, baserunning {}, defense {}", player.name, Stars(ratings.batting), Stars(ratings.baserunning), Stars(ratings.defense));
+    }
+    for &id in team.rotation.iter() {
+        let player = world.player(id);
+        println!("    {}: pitching {}", player.name, Stars(Ratings::new(player, &world.ruleset).pitching));
+    }
+}
+
+//runs by inning and then R/H/E. R is the score, so it takes sun 2 and black holes into account
+//where the innings don't, and what the teams started with. an X is a bottom half that never got played
+fn print_linescore(world: &World, game: &Game) {
+    let tenths = |runs: f64| (runs * 10.0).round() / 10.0; //floats
+    let innings = game.linescore.away.len().max(game.linescore.home.len());
+    let away = &world.team(game.scoreboard.away_team.id).name;
+    let home = &world.team(game.scoreboard.home_team.id).name;
+    let width = away.len().max(home.len());
+    let mut header = format!("{:width$}", "");
+    for inning in 1..innings {
+        header += &format!(" {:>3}", inning);
+    }
+    println!("{}     R   H   E", header);
+    for (name, top, score) in [(away, true, game.scoreboard.away_team.score), (home, false, game.scoreboard.home_team.score)] {
+        let mut row = format!("{:width$}", name);
+        for inning in 1..innings {
+            match game.linescore.line(top).get(inning) {
+                Some(inning) => row += &format!(" {:>3}", tenths(inning.runs)),
+                None => row += "   X",
+            }
+        }
+        println!("{} {:>5} {:>3} {:>3}", row, tenths(score), game.linescore.hits(top), game.linescore.errors(top));
+    }
 }
 
 fn main() {
@@ -49,14 +134,60 @@ fn main() {
     
     //let mut rng = Rng::new(12933895067857275469, 10184511423779887981); //s12 seed
 
+    //replaying one recorded game is its own thing, it doesn't need the rest of the setup
+    if let Some(path) = &args.replay {
+        let updates = get::game_updates_file(path).unwrap_or_else(|| panic!("couldn't read game updates from {}", path));
+        let rolls = replay::rolls(args.rolls.as_ref().expect("--replay needs --rolls"));
+        replay::replay(&updates, args.game, rolls, |constants| load_constants(constants, &args));
+        return;
+    }
+    if !args.calibrate.is_empty() {
+        let calibrations: Vec<calibrate::Calibration> = args.calibrate.iter()
+            .map(|&season| calibrate::tally(season, 99).unwrap_or_else(|| panic!("couldn't load game data for season {}", season + 1)))
+            .collect();
+        let mut constants = Constants::new(args.calibrate[0]);
+        load_constants(&mut constants, &args);
+        let fitted = calibrate::report(&calibrations, &constants);
+        if let Some(path) = &args.calibrateout {
+            calibrate::write(&fitted, &args.calibrate, path);
+        }
+        return;
+    }
+
     let mut rng = Rng::new(args.s1, args.s2);
     //let name_gen = NameGen::new();
     let mut prefill = args.prefill;
+    //resuming a real season from a given day (0-indexed)
+    let start_day = args.day.unwrap_or(0);
+    if args.day.is_some() && !(prefill && args.seasonmode) {
+        panic!("--day only works with --prefill and --seasonmode");
+    }
+    if args.validate && (!prefill || args.day.is_some()) {
+        panic!("--validate only works with --prefill, from the start of the season");
+    }
+    let mut ruleset = match Ruleset::new(args.season) {
+        Ok(ruleset) => ruleset,
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
+    load_constants(&mut ruleset.constants, &args);
     let mut world = if prefill {
-        world(args.season)
+        let snapshot = if let Some(day) = args.day {
+            Snapshot::day(args.season, day).unwrap_or_else(|| panic!("no games found on day {}", day))
+        } else {
+            Snapshot::season_start(args.season)
+        };
+        world(&snapshot, ruleset)
     } else {
-        World::new(args.season)
+        World::new(ruleset)
     }; //0-indexed season number
+    if prefill {
+        for (m, owners) in unknown_mods(&world) {
+            println!("Unrecognised mod {} ({}): {}", m, owners.len(), owners.join(", "));
+        }
+    }
 
     let team_number: usize = args.teams;
     let div_size: usize = args.divsize;
@@ -110,27 +241,55 @@ fn main() {
         //println!("{} {}", world.team(teams[i]).name, world.team(teams[i]).fate);
         if !prefill { fate_pool.retain(|&j| j != fate_roll) };
     }
-    let season_mode = args.seasonmode;
+    if let Some(path) = &args.export {
+        export(&world, path);
+    }
+    if args.roster {
+        for &team in divisions.iter() {
+            print_roster(&world, team, start_day);
+        }
+        return;
+    }
+    //load the real season first so a missing cache fails before simulating anything
+    let actual = if args.validate {
+        Some(validate::actual(args.season, 99).expect("couldn't load game data for validation"))
+    } else {
+        None
+    };
+    let mut validation_runs: Vec<validate::SeasonStats> = Vec::new();
+    let season_mode = args.seasonmode || args.validate;
     let loop_number = args.loops;
+    let mut playoff_appearances: Vec<usize> = vec![0; team_number];
+    let mut betting_totals: Vec<betting::Bettor> = Vec::new();
     for i in 0..loop_number {
         let mut og_world = world.clone();
         let mut sim = Sim::new(&mut og_world, &mut rng);
         if season_mode {
             let days_in_season = 99;
-            let game_number = team_number / 2;
-            let games = generate_games(generate_schedule(days_in_season, &divisions, sim.rng, team_number, div_size), sim.world, sim.rng);
-            for day in 0..days_in_season {
-                let mut games_active: Vec<Game> = Vec::new();
-                for i in (day * game_number)..((day + 1) * game_number) {
-                    games_active.push(games[i].clone());
-                }
-                for i in 0..game_number {
-                    let home_team = sim.world.team(games_active[i].scoreboard.home_team.id);
-                    let home_pitcher = home_team.rotation[day % home_team.rotation.len()];
-                    games_active[i].scoreboard.home_team.pitcher = if sim.world.player(home_pitcher).mods.has(Mod::Shelled) { home_team.rotation[(day - 1) % home_team.rotation.len()] } else { home_pitcher };
-                    let away_team = sim.world.team(games_active[i].scoreboard.away_team.id);
-                    let away_pitcher = away_team.rotation[day % away_team.rotation.len()];
-                    games_active[i].scoreboard.away_team.pitcher = if sim.world.player(away_pitcher).mods.has(Mod::Shelled) { away_team.rotation[(day - 1) % away_team.rotation.len()] } else { away_pitcher };
+            let schedule = if args.day.is_some() || args.validate {
+                get::schedule(args.season, start_day, days_in_season).expect("couldn't load the real schedule")
+            } else {
+                generate_schedule(days_in_season, &divisions, sim.rng, team_number, div_size)
+            };
+            let games = generate_games(schedule, sim.world, sim.rng);
+            let mut season_stats = validate::SeasonStats::new();
+            let mut bettors: Vec<betting::Bettor> = if args.betting {
+                betting::Strategy::ALL.iter().map(|&strategy| betting::Bettor::new(strategy, betting::STARTING_COINS, betting::MAX_BET)).collect()
+            } else {
+                Vec::new()
+            };
+            for day in start_day..days_in_season {
+                //the real schedule doesn't have the same number of games every day
+                let mut games_active: Vec<Game> = games.iter().filter(|g| g.day == day).cloned().collect();
+                for game in games_active.iter_mut() {
+                    game.prepare(sim.world);
+                    if args.betting {
+                        //on the day, since the pitchers and rosters change after the games are made
+                        let odds = game.odds(sim.world);
+                        for bettor in bettors.iter_mut() {
+                            bettor.bet(game, odds);
+                        }
+                    }
                 }
                 let mut games_deactivated: Vec<Uuid> = vec![];
                 loop {
@@ -138,10 +297,16 @@ fn main() {
                     for game in games_active.iter_mut() {
                         //todo: make games a field of Sim
                         let evt = sim.next(game);
+                        if args.validate {
+                            season_stats.record(&evt, game);
+                        }
                         // keeping sim outside the loop means it borrows world and we can't pass it as mut here, which might be fine...?
                         evt.apply(game, sim.world);
             
                         if let Event::GameOver = evt {
+                            for bettor in bettors.iter_mut() {
+                                bettor.settle(game);
+                            }
                             games_deactivated.push(game.id);
                         }
                     }
@@ -171,8 +336,18 @@ fn main() {
                 standings.push(team.wins);
                 println!("{}: {}-{}", team.name, team.wins, team.losses);
             }
+            if args.betting {
+                betting::merge(&mut betting_totals, &bettors);
+            }
+            if args.validate {
+                validation_runs.push(season_stats);
+                continue;
+            }
 
             let (mut playoff_seeds1, mut playoff_seeds2) = postseason::generate_seeding(&divisions, &standings, &fates, sim.rng);
+            for team in playoff_seeds1.iter().chain(playoff_seeds2.iter()) {
+                playoff_appearances[divisions.iter().position(|t| t == team).unwrap()] += 1;
+            }
 
             for team in playoff_seeds1.iter() {
                 println!("{}", sim.world.team(*team).name);
@@ -368,11 +543,21 @@ fn main() {
                 sim.world.team(game.scoreboard.home_team.id).name,
                 game.weather
             );
+            //to see where a matchup's chances come from, see explain.rs and how tests/explain.rs puts a Matchup together
+            //win probability is the home team's
+            let model = if args.winprob { Some(sandbox::markov::Model::new(&game, sim.world)) } else { None };
+            let mut win_probability = model.as_ref().map(|model| model.win_probability(&game, sim.world)).unwrap_or(0.0);
+            //(leverage, inning, what happened, change in win probability) for every plate appearance
+            let mut plays: Vec<(f64, String, String, f64)> = Vec::new();
+            let mut leverage = 0.0;
             loop {
                 let evt = sim.next(&game);
+                let batting = game.batter().is_some();
+                let runs_before = game.runs.len();
                 evt.apply(&mut game, sim.world);
 
                 if let Event::GameOver = evt {
+                    print_linescore(sim.world, &game);
                     println!(
                         "game over! {}: {}, {}: {}",
                         sim.world.team(game.scoreboard.away_team.id).name,
@@ -380,8 +565,31 @@ fn main() {
                         sim.world.team(game.scoreboard.home_team.id).name,
                         game.scoreboard.home_team.score
                     );
+                    if game.shame {
+                        println!("{} were shamed!", sim.world.team(game.scoreboard.away_team.id).name);
+                    }
+                    if model.is_none() {
+                        break;
+                    }
+                    //leverage index is leverage over the game's average
+                    let average = plays.iter().map(|play| play.0).sum::<f64>() / plays.len() as f64;
+                    plays.sort_by(|a, b| b.3.abs().total_cmp(&a.3.abs()));
+                    println!("biggest plays:");
+                    for (leverage, inning, play, swing) in plays.iter().take(5) {
+                        println!("{} {} ({:+.1}%, leverage index {:.2})", inning, play, 100.0 * swing, leverage / average);
+                    }
                     break;
                 }
+                if let Some(model) = &model {
+                    let previous = win_probability;
+                    win_probability = model.win_probability(&game, sim.world);
+                    if let Event::BatterUp { .. } = evt {
+                        leverage = model.leverage(&game, sim.world);
+                    } else if batting && game.batter().is_none() {
+                        let inning = format!("{}{}", if game.scoreboard.top { "t" } else { "b" }, game.inning);
+                        plays.push((leverage, inning, format!("{:?}", evt), win_probability - previous));
+                    }
+                }
                 let base = if game.runners.base_number == 5 {
                     format!(
                     "[{}|{}|{}|{}]",
@@ -403,7 +611,7 @@ fn main() {
                 let home_score = (game.scoreboard.home_team.score * 10.0).round() / 10.0; //floats
 
                 println!(
-                    "{}{} {}@{} ({}b/{}s/{}o) {} {:?}",
+                    "{}{} {}@{} ({}b/{}s/{}o) {}{} {:?}",
                     if game.scoreboard.top { "t" } else { "b" },
                     game.inning,
                     away_score,
@@ -412,10 +620,32 @@ fn main() {
                     game.strikes,
                     game.outs,
                     base,
+                    if model.is_some() { format!(" {:.1}%", 100.0 * win_probability) } else { String::new() },
                     evt
                 );
+                for run in game.runs[runs_before..].iter() {
+                    println!(
+                        "    {} scores ({:+}), {}charged to {}",
+                        sim.world.player(run.runner).name,
+                        run.value,
+                        run.batter.map(|batter| format!("batted in by {}, ", sim.world.player(batter).name)).unwrap_or_default(),
+                        sim.world.player(run.pitcher).name
+                    );
+                }
             }
         }
         // println!("Hello, world!");
     }
+    if let Some(actual) = actual {
+        validate::report(&actual, &validation_runs, &divisions, &world);
+    }
+    if !betting_totals.is_empty() {
+        betting::report(&betting_totals, loop_number);
+    }
+    if let Some(day) = args.day {
+        println!("Playoff odds from day {}:", day + 1);
+        for i in 0..team_number {
+            println!("{}: {:.1}%", world.team(divisions[i]).name, 100.0 * playoff_appearances[i] as f64 / loop_number as f64);
+        }
+    }
 }
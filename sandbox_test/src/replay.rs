@@ -0,0 +1,202 @@
+use std::fs;
+
+use crate::{feed::{classify, from_event, plays, FeedEvent}, get::{world, ChronGameState, ChronGameUpdate, Snapshot}};
+use sandbox::{constants::Constants, events::Event, rng::Replay, ruleset::Ruleset, sim::Sim, Game, Weather};
+use uuid::Uuid;
+
+//rolls from a file, one per line
+//resim's csv roll logs work too, the roll is the first column that's a number in [0, 1)
+pub fn rolls(path: &str) -> Vec<f64> {
+    let file = fs::read_to_string(path).unwrap_or_else(|e| panic!("couldn't read rolls from {}: {}", path, e));
+    file.lines()
+        .filter_map(|line| line.split(',').find_map(|field| field.trim().parse::<f64>().ok().filter(|r| (0.0..1.0).contains(r))))
+        .collect()
+}
+
+//to a tenth, with no negative zero: a game that starts with no shame runs taken off is at -0
+fn score(score: f64) -> String {
+    format!("{:.1}", (score * 10.0).round() / 10.0 + 0.0)
+}
+
+//everything that doesn't match between sandbox's game and the recorded one
+fn diff(game: &Game, state: &ChronGameState) -> Vec<String> {
+    let mut diffs = Vec::new();
+    let mut check = |name: &str, sim: String, real: String| {
+        if sim != real {
+            diffs.push(format!("{}: sandbox {}, recorded {}", name, sim, real));
+        }
+    };
+    check("inning", game.inning.to_string(), (state.inning + 1).to_string());
+    check("top", game.scoreboard.top.to_string(), state.topOfInning.to_string());
+    check("balls", game.balls.to_string(), state.atBatBalls.to_string());
+    check("strikes", game.strikes.to_string(), state.atBatStrikes.to_string());
+    check("home score", score(game.scoreboard.home_team.score), score(state.homeScore));
+    check("away score", score(game.scoreboard.away_team.score), score(state.awayScore));
+    //sandbox keeps the third out and the stranded runners around until the inning switch,
+    //blaseball clears them straight away
+    if game.outs < 3 {
+        check("outs", game.outs.to_string(), state.halfInningOuts.to_string());
+        let mut sim_runners: Vec<String> = game.runners.iter().map(|r| format!("{}@{}", r.id, r.base)).collect();
+        let mut real_runners: Vec<String> = state.baseRunners.iter().zip(state.basesOccupied.iter()).map(|(id, base)| format!("{}@{}", id, base)).collect();
+        sim_runners.sort();
+        real_runners.sort();
+        check("runners", format!("{:?}", sim_runners), format!("{:?}", real_runners));
+    } else {
+        check("outs", "0".to_string(), state.halfInningOuts.to_string());
+    }
+    diffs
+}
+
+//drives a game with recorded rolls and checks every tick against the recorded updates,
+//stopping at the first divergence. returns whether the whole game matched
+pub fn replay(updates: &[ChronGameUpdate], game_id: Option<Uuid>, rolls: Vec<f64>, setup: impl FnOnce(&mut Constants)) -> bool {
+    let (id, plays) = match game_id {
+        Some(id) => plays(updates).into_iter().find(|(g, _)| *g == id).unwrap_or_else(|| panic!("game {} not found in updates", id)),
+        None => plays(updates).into_iter().next().expect("no game updates to replay")
+    };
+    //pregame updates and the "Top of 1" announcement don't have sandbox events,
+    //so the game starts from the update just before the first batter
+    let first = plays.iter().position(|u| classify(&u.data.lastUpdate) == FeedEvent::BatterUp).unwrap_or(1).max(1);
+    let start = &plays[first - 1].data;
+
+    let snapshot = Snapshot::day(start.season, start.day).unwrap_or_else(|| panic!("no games found on day {}", start.day));
+    let mut ruleset = match Ruleset::new(start.season) {
+        Ok(ruleset) => ruleset,
+        Err(e) => {
+            println!("{}", e);
+            return false;
+        }
+    };
+    setup(&mut ruleset.constants);
+    let mut world = world(&snapshot, ruleset);
+    let weather = start.weather.and_then(Weather::from_id).unwrap_or_else(|| panic!("unsupported weather {:?}", start.weather));
+    let mut rng = Replay::new(rolls);
+    let mut game = Game::new(start.homeTeam, start.awayTeam, start.day, Some(weather), &world, &mut rng);
+    game.id = id;
+    if let Some(pitcher) = start.homePitcher {
+        game.scoreboard.home_team.pitcher = pitcher;
+    }
+    if let Some(pitcher) = start.awayPitcher {
+        game.scoreboard.away_team.pitcher = pitcher;
+    }
+
+    let mut sim = Sim::new(&mut world, &mut rng);
+    for (tick, update) in plays.iter().enumerate().skip(first) {
+        let rolls_before = sim.rng.position();
+        let evt = sim.next(&game);
+        evt.apply(&mut game, sim.world);
+
+        //whatever the game did with the zeroes past the end doesn't mean anything, so this goes first
+        if sim.rng.exhausted() {
+            println!("Diverged at tick {} ({}): ran out of rolls, the sim wanted {} and only {} were recorded", tick - first, update.timestamp, sim.rng.position(), sim.rng.recorded());
+            return false;
+        }
+        let mut diffs = diff(&game, &update.data);
+        let recorded = classify(&update.data.lastUpdate);
+        if from_event(&evt) != recorded {
+            diffs.insert(0, format!("event: sandbox {:?}, recorded {:?}", from_event(&evt), recorded));
+        }
+        if !diffs.is_empty() {
+            println!("Diverged at tick {} ({}), rolls {}..{}", tick - first, update.timestamp, rolls_before, sim.rng.position());
+            println!("sandbox: {:?}", evt);
+            println!("recorded: {}", update.data.lastUpdate);
+            for d in diffs {
+                println!("    {}", d);
+            }
+            return false;
+        }
+        if let Event::GameOver = evt {
+            break;
+        }
+    }
+    println!("Replayed {} ticks of {} using {} rolls, {} left over", plays.len() - first, id, sim.rng.position(), sim.rng.remaining());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sandbox::{entities::World, rng::Rng};
+
+    fn game() -> Game {
+        let mut rng = Rng::new(69, 420);
+        let mut world = World::new(Ruleset::new(11).unwrap());
+        let away = world.gen_team(&mut rng, "Away".to_string(), "A".to_string());
+        let home = world.gen_team(&mut rng, "Home".to_string(), "H".to_string());
+        Game::new(home, away, 0, Some(Weather::Sun), &world, &mut rng)
+    }
+
+    //the recorded state of a game that's just started, to change bits of
+    fn state(game: &Game) -> ChronGameState {
+        serde_json::from_value(serde_json::json!({
+            "day": 0,
+            "season": 11,
+            "weather": 1,
+            "homeTeam": game.scoreboard.home_team.id,
+            "awayTeam": game.scoreboard.away_team.id,
+            "homeScore": 0.0,
+            "awayScore": 0.0,
+            "inning": 0,
+            "topOfInning": true,
+            "halfInningOuts": 0,
+            "atBatBalls": 0,
+            "atBatStrikes": 0,
+            "homeBatter": null,
+            "awayBatter": null,
+            "homePitcher": null,
+            "awayPitcher": null,
+            "playCount": 0
+        })).unwrap()
+    }
+
+    #[test]
+    fn matching_states_have_no_diff() {
+        let game = game();
+        assert_eq!(diff(&game, &state(&game)), Vec::<String>::new());
+    }
+
+    #[test]
+    fn every_mismatch_is_listed() {
+        let game = game();
+        let mut recorded = state(&game);
+        recorded.atBatBalls = 2;
+        recorded.homeScore = 1.0;
+        recorded.inning = 1;
+        let diffs = diff(&game, &recorded);
+        assert_eq!(diffs, vec![
+            "inning: sandbox 1, recorded 2".to_string(),
+            "balls: sandbox 0, recorded 2".to_string(),
+            "home score: sandbox 0.0, recorded 1.0".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn runners_are_compared_in_any_order() {
+        let mut game = game();
+        let pitcher = game.pitcher();
+        let (first, second) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        game.runners.add(0, first, pitcher);
+        game.runners.add(1, second, pitcher);
+        let mut recorded = state(&game);
+        recorded.baseRunners = vec![second, first];
+        recorded.basesOccupied = vec![1, 0];
+        assert!(diff(&game, &recorded).is_empty());
+        //but the wrong runner on a base is a diff
+        recorded.basesOccupied = vec![0, 1];
+        assert_eq!(diff(&game, &recorded).len(), 1);
+    }
+
+    #[test]
+    fn the_third_out_is_cleared_straight_away() {
+        let mut game = game();
+        let pitcher = game.pitcher();
+        game.outs = 3;
+        game.runners.add(2, Uuid::from_u128(1), pitcher);
+        //blaseball's already reset the outs and the stranded runner
+        let recorded = state(&game);
+        assert!(diff(&game, &recorded).is_empty());
+        let mut recorded = state(&game);
+        recorded.halfInningOuts = 3;
+        assert_eq!(diff(&game, &recorded), vec!["outs: sandbox 0, recorded 3".to_string()]);
+    }
+}
@@ -52,7 +52,8 @@ pub fn generate_schedule(days: usize,  divisions: &Vec<Uuid>, rng: &mut Rng, lea
                     day,
                     order,
                     home_team: divisions[home_team_num],
-                    away_team: divisions[away_team_num]
+                    away_team: divisions[away_team_num],
+                    weather: None
                 });
                 for k in 0..4 {
                     remaining_teams[k].retain(|&n| n != home_team_num && n != away_team_num);
@@ -100,7 +101,8 @@ pub fn generate_schedule(days: usize,  divisions: &Vec<Uuid>, rng: &mut Rng, lea
                     day,
                     order,
                     home_team: divisions[home_team_num],
-                    away_team: divisions[away_team_num]
+                    away_team: divisions[away_team_num],
+                    weather: None
                 });
                 for k in 0..2 {
                     remaining_teams[k].retain(|&n| n != home_team_num && n != away_team_num);
@@ -127,7 +129,8 @@ pub fn generate_schedule(days: usize,  divisions: &Vec<Uuid>, rng: &mut Rng, lea
                     day,
                     order,
                     home_team: divisions[home_team_num],
-                    away_team: divisions[away_team_num]
+                    away_team: divisions[away_team_num],
+                    weather: None
                 });
                 for k in 2..4 {
                     remaining_teams[k].retain(|&n| n != home_team_num && n != away_team_num);
@@ -154,7 +157,8 @@ pub fn generate_schedule(days: usize,  divisions: &Vec<Uuid>, rng: &mut Rng, lea
                         day,
                         order,
                         home_team: divisions[home_team_num],
-                        away_team: divisions[away_team_num]
+                        away_team: divisions[away_team_num],
+                        weather: None
                     });
                     remaining_teams[j].retain(|&n| n != home_team_num && n != away_team_num);
                     orders.retain(|&n| n != order);
@@ -191,7 +195,7 @@ pub fn generate_schedule(days: usize,  divisions: &Vec<Uuid>, rng: &mut Rng, lea
 }
 
 pub fn generate_games(schedule: Vec<ScheduleGame>, world: &World, rng: &mut Rng) -> Vec<Game> {
-    schedule.iter().map(|sg| Game::new(sg.home_team, sg.away_team, sg.day, None, world, rng)).collect()
+    schedule.iter().map(|sg| Game::new(sg.home_team, sg.away_team, sg.day, sg.weather.clone(), world, rng)).collect()
 }
 
 #[derive(Debug, Clone)]
@@ -199,5 +203,18 @@ pub struct ScheduleGame {
     day: usize,
     order: usize,
     home_team: Uuid,
-    away_team: Uuid
+    away_team: Uuid,
+    weather: Option<Weather> //rolled at game creation if None
+}
+
+impl ScheduleGame {
+    pub fn new(day: usize, order: usize, home_team: Uuid, away_team: Uuid, weather: Option<Weather>) -> ScheduleGame {
+        ScheduleGame {
+            day,
+            order,
+            home_team,
+            away_team,
+            weather
+        }
+    }
 }
@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+
+use crate::{feed::{classify, from_event, plays, FeedEvent}, get};
+use sandbox::{entities::World, events::Event, Game};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Default)]
+pub struct TeamStats {
+    pub wins: usize,
+    pub runs: f64,
+    pub plate_appearances: usize,
+    pub hits: usize,
+    pub home_runs: usize,
+    pub strikeouts: usize,
+}
+
+//regular season totals, either from a simulated season or from chronicler
+#[derive(Clone, Debug, Default)]
+pub struct SeasonStats {
+    pub teams: BTreeMap<Uuid, TeamStats>,
+    pub incinerations: usize,
+    pub peanuts: usize,
+    pub feedbacks: usize,
+}
+
+impl SeasonStats {
+    pub fn new() -> SeasonStats {
+        SeasonStats::default()
+    }
+
+    fn feed(&mut self, event: FeedEvent, team: Uuid) {
+        let stats = self.teams.entry(team).or_default();
+        if event.plate_appearance() {
+            stats.plate_appearances += 1;
+        }
+        match event {
+            FeedEvent::Hit => stats.hits += 1,
+            FeedEvent::HomeRun => {
+                stats.hits += 1;
+                stats.home_runs += 1;
+            },
+            FeedEvent::Strikeout => stats.strikeouts += 1,
+            FeedEvent::Incineration => self.incinerations += 1,
+            FeedEvent::Peanut => self.peanuts += 1,
+            FeedEvent::Feedback => self.feedbacks += 1,
+            _ => {}
+        }
+    }
+
+    fn game_over(&mut self, home_team: Uuid, away_team: Uuid, home_score: f64, away_score: f64) {
+        self.teams.entry(home_team).or_default().runs += home_score;
+        self.teams.entry(away_team).or_default().runs += away_score;
+        let winner = if home_score > away_score { home_team } else { away_team };
+        self.teams.entry(winner).or_default().wins += 1;
+    }
+
+    //has to be called before the event is applied, so that the batting team is the one that did the thing
+    pub fn record(&mut self, evt: &Event, game: &Game) {
+        if let Event::GameOver = evt {
+            let scoreboard = &game.scoreboard;
+            self.game_over(scoreboard.home_team.id, scoreboard.away_team.id, scoreboard.home_team.score, scoreboard.away_team.score);
+            return;
+        }
+        let event = from_event(evt);
+        self.feed(event, game.scoreboard.batting_team().id);
+    }
+}
+
+//the real regular season, from cached chronicler games and game updates
+pub fn actual(season: u8, days_in_season: usize) -> Option<SeasonStats> {
+    let mut stats = SeasonStats::new();
+    for game in get::games(season)?.data.into_iter().map(|g| g.data) {
+        if game.day < days_in_season && game.gameComplete {
+            stats.game_over(game.homeTeam, game.awayTeam, game.homeScore, game.awayScore);
+        }
+    }
+    for day in 0..days_in_season {
+        let updates = get::game_updates(season, day)?;
+        for (_, game) in plays(&updates) {
+            for update in game {
+                let batting_team = if update.data.topOfInning { update.data.awayTeam } else { update.data.homeTeam };
+                stats.feed(classify(&update.data.lastUpdate), batting_team);
+            }
+        }
+    }
+    Some(stats)
+}
+
+fn mean_sd(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    if samples.len() < 2 {
+        return (mean, 0.0);
+    }
+    let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+    (mean, var.sqrt())
+}
+
+//abramowitz-stegun 7.1.26, good to about 1e-7
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let y = 1.0 - t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429)))) * (-x * x).exp();
+    if x < 0.0 { -y } else { y }
+}
+
+struct Comparison {
+    name: String,
+    actual: f64,
+    mean: f64,
+    sd: f64,
+    z: f64,
+    p: f64,
+}
+
+//how surprising the real value is if it were one more draw from the simulated seasons
+fn compare(name: String, actual: f64, samples: &[f64]) -> Comparison {
+    let (mean, sd) = mean_sd(samples);
+    let spread = sd * (1.0 + 1.0 / samples.len() as f64).sqrt();
+    let z = if spread > 0.0 {
+        (actual - mean) / spread
+    } else if actual == mean {
+        0.0
+    } else {
+        f64::INFINITY
+    };
+    let p = 1.0 - erf(z.abs() / 2f64.sqrt());
+    Comparison { name, actual, mean, sd, z, p }
+}
+
+fn rate(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 { 0.0 } else { numerator as f64 / denominator as f64 }
+}
+
+type TeamStat = (&'static str, fn(&TeamStats) -> f64);
+type SeasonStat = (&'static str, fn(&SeasonStats) -> f64);
+
+//mismatches are judged at p < 0.05 after a bonferroni correction,
+//since with five stats for every team (100 checks with the default 20) a few would come up by chance otherwise
+pub fn report(actual: &SeasonStats, sims: &[SeasonStats], teams: &[Uuid], world: &World) {
+    let mut comparisons: Vec<Comparison> = Vec::new();
+    let default = TeamStats::default();
+    for &team in teams {
+        let name = &world.team(team).name;
+        let real = actual.teams.get(&team).unwrap_or(&default);
+        let simulated: Vec<&TeamStats> = sims.iter().map(|s| s.teams.get(&team).unwrap_or(&default)).collect();
+        let stats: [TeamStat; 5] = [
+            ("wins", |t| t.wins as f64),
+            ("runs", |t| t.runs),
+            ("hits/PA", |t| rate(t.hits, t.plate_appearances)),
+            ("HR/PA", |t| rate(t.home_runs, t.plate_appearances)),
+            ("K/PA", |t| rate(t.strikeouts, t.plate_appearances)),
+        ];
+        for (stat, f) in stats {
+            let samples: Vec<f64> = simulated.iter().map(|t| f(t)).collect();
+            comparisons.push(compare(format!("{} {}", name, stat), f(real), &samples));
+        }
+    }
+    let weather: [SeasonStat; 3] = [
+        ("incinerations", |s| s.incinerations as f64),
+        ("peanuts", |s| s.peanuts as f64),
+        ("feedback swaps", |s| s.feedbacks as f64),
+    ];
+    for (stat, f) in weather {
+        let samples: Vec<f64> = sims.iter().map(f).collect();
+        comparisons.push(compare(stat.to_string(), f(actual), &samples));
+    }
+
+    let threshold = 0.05 / comparisons.len() as f64;
+    println!("Validation against {} simulated seasons:", sims.len());
+    for c in comparisons.iter() {
+        println!(
+            "{}: actual {:.3}, sim {:.3} ± {:.3}, z {:.2}, p {:.4}{}",
+            c.name, c.actual, c.mean, c.sd, c.z, c.p,
+            if c.p < threshold { " MISMATCH" } else { "" }
+        );
+    }
+    let mismatches = comparisons.iter().filter(|c| c.p < threshold).count();
+    println!("{} of {} stats mismatched (p < {:.5})", mismatches, comparisons.len(), threshold);
+}